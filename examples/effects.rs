@@ -0,0 +1,64 @@
+use genoise::effect::Dispatcher;
+use genoise::Generator as _;
+use library::{do_something, HttpRequest, PayloadLen};
+
+const SPECIAL_CASE: &str = "rust-lang.org";
+
+fn main() {
+    println!("Calling `do_something` with {:?}", SPECIAL_CASE);
+    let generator = do_something(SPECIAL_CASE);
+
+    // Each effect is handled by a dedicated closure, receiving the effect with its actual type
+    // and returning the response type associated to it
+    let dispatcher = Dispatcher::new()
+        .on(|HttpRequest { url }| {
+            assert_eq!(url, SPECIAL_CASE);
+            dbg!(vec![1, 2, 3])
+        })
+        .on(|PayloadLen(len)| dbg!(u32::try_from(len).unwrap()));
+
+    println!("Drive the generator");
+    let out = generator.run_with(dispatcher);
+    assert_eq!(dbg!(out), 3);
+}
+
+mod library {
+    use genoise::effect::{Effect, Request, Response};
+    use genoise::{local, Co, GeneratorFlavor};
+
+    // Effects our generator performs, each one associated to the type of its response
+
+    pub struct HttpRequest {
+        pub url: String,
+    }
+
+    impl Effect for HttpRequest {
+        type Response = Vec<u8>;
+    }
+
+    pub struct PayloadLen(pub usize);
+
+    impl Effect for PayloadLen {
+        type Response = u32;
+    }
+
+    pub fn do_something(url: &str) -> local::Gn<'_, '_, Request, Response, u32> {
+        local::Gn::new(|co| async { do_something_impl(co, url).await })
+    }
+
+    // No need to check the variant of the resume value anymore: the response type is known
+    // statically for each effect
+
+    async fn do_something_impl<F: GeneratorFlavor>(
+        mut co: Co<'_, Request, Response, F>,
+        url: &str,
+    ) -> u32 {
+        let payload = co
+            .perform(HttpRequest {
+                url: url.to_owned(),
+            })
+            .await;
+
+        co.perform(PayloadLen(payload.len())).await
+    }
+}
@@ -4,6 +4,9 @@ fn main() {
     let it = DeepIterator::new(&[&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]]);
     let expected = &[1, 2, 3, 4, 5, 6, 7, 8, 9];
 
+    // The generator advertises its exact length before being started
+    assert_eq!(it.len(), 9);
+
     for (expected, actual) in expected.iter().zip(it) {
         assert_eq!(actual, expected);
         println!("{actual}");
@@ -24,9 +27,7 @@ mod library {
         }
 
         for slice in slice_of_slices {
-            for value in *slice {
-                co.suspend(value).await;
-            }
+            co.suspend_iter(*slice).await;
         }
     }
 
@@ -38,9 +39,15 @@ mod library {
 
     impl<'a, T> DeepIterator<'a, T> {
         pub fn new(slice_of_slices: &'a [&'a [T]]) -> Self {
-            let inner = local::Gn::new(|co| deep_iterator_impl(co, slice_of_slices));
+            let len = slice_of_slices.iter().map(|slice| slice.len()).sum();
+            let inner = local::Gn::new(|co| deep_iterator_impl(co, slice_of_slices))
+                .with_size_hint(len, Some(len));
             Self { inner }
         }
+
+        pub fn len(&self) -> usize {
+            self.inner.yield_hint().0
+        }
     }
 
     impl<'a, T> IntoIterator for DeepIterator<'a, T> {
@@ -1,52 +1,37 @@
+use genoise::duplex::{self, Message, Turn};
 use genoise::local;
-use genoise::GnState;
-
-enum StateMachineResponse {
-    Waiting,
-    SendPayload(String),
-}
 
 async fn tls_client_sequence(
-    mut co: local::StackCo<'_, StateMachineResponse, String>,
+    mut co: local::StackCo<'_, Turn<String>, Option<String>>,
 ) -> Result<(), ()> {
     // Send ClientHello message
-    let received = co
-        .suspend(StateMachineResponse::SendPayload("ClientHello".to_owned()))
-        .await;
+    let received = co.suspend(Turn::Send("ClientHello".to_owned())).await;
 
     // Check received message
-    if received != "ServerHello" {
+    if received.as_deref() != Some("ServerHello") {
         return Err(());
     }
 
-    while co.suspend(StateMachineResponse::Waiting).await != "ServerHelloDone" {
+    while co.suspend(Turn::Waiting).await.as_deref() != Some("ServerHelloDone") {
         // Process initial negotiation messages from server
     }
 
     // Generate and exchange the client key
-    co.suspend(StateMachineResponse::SendPayload(
-        "ClientKeyExchange".to_owned(),
-    ))
-    .await;
+    co.suspend(Turn::Send("ClientKeyExchange".to_owned())).await;
 
     // Tell the server to change to encrypted mode
-    co.suspend(StateMachineResponse::SendPayload(
-        "ChangeCipherSpec".to_owned(),
-    ))
-    .await;
+    co.suspend(Turn::Send("ChangeCipherSpec".to_owned())).await;
 
     // Tell the server that we are ready for secure data communication to begin
-    let received = co
-        .suspend(StateMachineResponse::SendPayload("Finished".to_owned()))
-        .await;
+    let received = co.suspend(Turn::Send("Finished".to_owned())).await;
 
-    if received != "ChangeCipherSpec" {
+    if received.as_deref() != Some("ChangeCipherSpec") {
         return Err(());
     }
 
-    let received = co.suspend(StateMachineResponse::Waiting).await;
+    let received = co.suspend(Turn::Waiting).await;
 
-    if received != "Finished" {
+    if received.as_deref() != Some("Finished") {
         return Err(());
     }
 
@@ -54,46 +39,33 @@ async fn tls_client_sequence(
 }
 
 async fn tls_server_sequence(
-    mut co: local::StackCo<'_, StateMachineResponse, String>,
+    mut co: local::StackCo<'_, Turn<String>, Option<String>>,
 ) -> Result<(), ()> {
-    let received = co.suspend(StateMachineResponse::Waiting).await;
+    let received = co.suspend(Turn::Waiting).await;
 
-    if received != "ClientHello" {
+    if received.as_deref() != Some("ClientHello") {
         return Err(());
     }
 
-    co.suspend(StateMachineResponse::SendPayload("ServerHello".to_owned()))
-        .await;
+    co.suspend(Turn::Send("ServerHello".to_owned())).await;
 
-    co.suspend(StateMachineResponse::SendPayload("Certificate".to_owned()))
-        .await;
+    co.suspend(Turn::Send("Certificate".to_owned())).await;
 
-    co.suspend(StateMachineResponse::SendPayload(
-        "ServerKeyExchange".to_owned(),
-    ))
-    .await;
+    co.suspend(Turn::Send("ServerKeyExchange".to_owned())).await;
 
-    let received = co
-        .suspend(StateMachineResponse::SendPayload(
-            "ServerHelloDone".to_owned(),
-        ))
-        .await;
+    let received = co.suspend(Turn::Send("ServerHelloDone".to_owned())).await;
 
-    if received != "ClientKeyExchange" {
+    if received.as_deref() != Some("ClientKeyExchange") {
         return Err(());
     }
 
-    while co.suspend(StateMachineResponse::Waiting).await != "Finished" {
+    while co.suspend(Turn::Waiting).await.as_deref() != Some("Finished") {
         // Process initial negotiation messages from server
     }
 
-    co.suspend(StateMachineResponse::SendPayload(
-        "ChangeCipherSpec".to_owned(),
-    ))
-    .await;
+    co.suspend(Turn::Send("ChangeCipherSpec".to_owned())).await;
 
-    co.suspend(StateMachineResponse::SendPayload("Finished".to_owned()))
-        .await;
+    co.suspend(Turn::Send("Finished".to_owned())).await;
 
     Ok(())
 }
@@ -102,40 +74,15 @@ fn main() -> Result<(), ()> {
     local::let_gen!(client_sequence, tls_client_sequence);
     local::let_gen!(server_sequence, tls_server_sequence);
 
-    let mut client_state = client_sequence.start();
-    let _ = server_sequence.start();
-
-    loop {
-        let client_response = match client_state {
-            GnState::Suspended(StateMachineResponse::Waiting) => String::new(),
-            GnState::Suspended(StateMachineResponse::SendPayload(payload)) => payload,
-            GnState::Completed(res) => {
-                res?;
-                break;
-            }
-        };
-
-        if !client_response.is_empty() {
-            println!("-> {client_response}");
-        }
-
-        let server_state = server_sequence.resume(client_response);
-
-        let server_response = match server_state {
-            GnState::Suspended(StateMachineResponse::Waiting) => String::new(),
-            GnState::Suspended(StateMachineResponse::SendPayload(payload)) => payload,
-            GnState::Completed(res) => {
-                res?;
-                break;
-            }
-        };
-
-        if !server_response.is_empty() {
-            println!("<- {server_response}");
-        }
-
-        client_state = client_sequence.resume(server_response);
-    }
+    let (client_result, server_result) =
+        duplex::run(client_sequence, server_sequence, |message| match message {
+            Message::Initiator(payload) => println!("-> {payload}"),
+            Message::Responder(payload) => println!("<- {payload}"),
+        })
+        .expect("client and server agree on the sequence");
+
+    client_result?;
+    server_result?;
 
     println!("Done.");
 
@@ -31,42 +31,35 @@ fn main() {
 }
 
 fn drive_generator<F>(
-    mut generator: genoise::Gn<'_, '_, Event<'_>, UserResponse, u32, F>,
+    generator: genoise::Gn<'_, '_, Event<'_>, UserResponse, u32, F>,
     expected_url: &str,
     expected_payload_len: usize,
 ) -> u32
 where
     F: genoise::GeneratorFlavor,
 {
-    use genoise::GnState;
-
-    // Start the generator
-    let mut state = generator.start();
-
-    loop {
-        let response = match state {
-            // The generator is suspended, handle the yielded value
-            GnState::Suspended(event) => {
-                // How the events are actually handled is up to the caller
-                // (could perform I/O with or without async)
-                match event {
-                    Event::HttpRequest { url } => {
-                        assert_eq!(url, expected_url);
-                        UserResponse::Payload(vec![1, 2, 3])
-                    }
-                    Event::PayloadLen(len) => {
-                        assert_eq!(len, expected_payload_len);
-                        UserResponse::SomeValue(u32::try_from(len).unwrap())
-                    }
-                }
+    use genoise::Generator as _;
+
+    // Drive the generator until completion, handling each yielded value
+    let out = generator.run_with(|event| {
+        // How the events are actually handled is up to the caller
+        // (could perform I/O with or without async)
+        let response = match event {
+            Event::HttpRequest { url } => {
+                assert_eq!(url, expected_url);
+                UserResponse::Payload(vec![1, 2, 3])
+            }
+            Event::PayloadLen(len) => {
+                assert_eq!(len, expected_payload_len);
+                UserResponse::SomeValue(u32::try_from(len).unwrap())
             }
-            // The generator is in its final state, break out the execution loop
-            GnState::Completed(out) => break dbg!(out),
         };
 
-        // Resume the generator
-        state = generator.resume(dbg!(response));
-    }
+        // The generator is resumed with the returned value
+        dbg!(response)
+    });
+
+    dbg!(out)
 }
 
 mod library {
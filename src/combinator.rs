@@ -0,0 +1,538 @@
+//! Generator adapters
+//!
+//! Adapters are built using the provided methods of the [`Generator`] trait, and are generators
+//! themselves. They wrap the underlying generator by value, so no additional allocation happens
+//! regardless of the flavor.
+//!
+//! ```
+//! use genoise::local::{Co, Gn};
+//! use genoise::{Generator as _, GnState};
+//!
+//! async fn count(mut co: Co<'_, u32, bool>) -> u32 {
+//!     let mut count = 0;
+//!     while co.suspend(count).await {
+//!         count += 1;
+//!     }
+//!     count
+//! }
+//!
+//! let mut generator = Gn::new(count)
+//!     .map_yield(|count| count * 10)
+//!     .map_resume(|stop: &str| stop != "stop")
+//!     .map_output(|count| format!("counted up to {count}"));
+//!
+//! assert!(matches!(generator.start(), GnState::Suspended(0)));
+//! assert!(matches!(generator.resume("continue"), GnState::Suspended(10)));
+//! let GnState::Completed(output) = generator.resume("stop") else {
+//!     unreachable!()
+//! };
+//! assert_eq!(output, "counted up to 1");
+//! ```
+
+use core::marker::PhantomData;
+
+use crate::{Generator, GnError, GnState};
+
+/// A generator transforming the values yielded by another generator
+///
+/// Returned by [`Generator::map_yield`].
+#[must_use = "generators do nothing unless you `.start()` or `.resume(…)` them"]
+pub struct MapYield<G, Func> {
+    generator: G,
+    f: Func,
+}
+
+impl<G, Func> MapYield<G, Func> {
+    pub(crate) fn new(generator: G, f: Func) -> Self {
+        Self { generator, f }
+    }
+
+    /// Returns the underlying generator
+    pub fn into_inner(self) -> G {
+        self.generator
+    }
+}
+
+impl<G, Func, Y2> MapYield<G, Func>
+where
+    G: Generator,
+    Func: FnMut(G::Yield) -> Y2,
+{
+    fn map(&mut self, state: GnState<G::Yield, G::Output>) -> GnState<Y2, G::Output> {
+        match state {
+            GnState::Suspended(value) => GnState::Suspended((self.f)(value)),
+            GnState::Completed(value) => GnState::Completed(value),
+        }
+    }
+}
+
+impl<G, Func, Y2> Generator for MapYield<G, Func>
+where
+    G: Generator,
+    Func: FnMut(G::Yield) -> Y2,
+{
+    type Yield = Y2;
+    type Resume = G::Resume;
+    type Output = G::Output;
+
+    fn try_start(&mut self) -> Result<GnState<Y2, G::Output>, GnError> {
+        let state = self.generator.try_start()?;
+        Ok(self.map(state))
+    }
+
+    fn try_start_with(&mut self, value: G::Resume) -> Result<GnState<Y2, G::Output>, GnError> {
+        let state = self.generator.try_start_with(value)?;
+        Ok(self.map(state))
+    }
+
+    fn try_resume(&mut self, value: G::Resume) -> Result<GnState<Y2, G::Output>, GnError> {
+        let state = self.generator.try_resume(value)?;
+        Ok(self.map(state))
+    }
+
+    fn started(&self) -> bool {
+        self.generator.started()
+    }
+
+    fn is_completed(&self) -> bool {
+        self.generator.is_completed()
+    }
+}
+
+/// A generator transforming the values passed in before resuming another generator
+///
+/// Returned by [`Generator::map_resume`].
+#[must_use = "generators do nothing unless you `.start()` or `.resume(…)` them"]
+pub struct MapResume<G, Func, R2> {
+    generator: G,
+    f: Func,
+    _resume: PhantomData<fn(R2)>,
+}
+
+impl<G, Func, R2> MapResume<G, Func, R2> {
+    pub(crate) fn new(generator: G, f: Func) -> Self {
+        Self {
+            generator,
+            f,
+            _resume: PhantomData,
+        }
+    }
+
+    /// Returns the underlying generator
+    pub fn into_inner(self) -> G {
+        self.generator
+    }
+}
+
+impl<G, Func, R2> Generator for MapResume<G, Func, R2>
+where
+    G: Generator,
+    Func: FnMut(R2) -> G::Resume,
+{
+    type Yield = G::Yield;
+    type Resume = R2;
+    type Output = G::Output;
+
+    fn try_start(&mut self) -> Result<GnState<G::Yield, G::Output>, GnError> {
+        self.generator.try_start()
+    }
+
+    fn try_start_with(&mut self, value: R2) -> Result<GnState<G::Yield, G::Output>, GnError> {
+        self.generator.try_start_with((self.f)(value))
+    }
+
+    fn try_resume(&mut self, value: R2) -> Result<GnState<G::Yield, G::Output>, GnError> {
+        self.generator.try_resume((self.f)(value))
+    }
+
+    fn started(&self) -> bool {
+        self.generator.started()
+    }
+
+    fn is_completed(&self) -> bool {
+        self.generator.is_completed()
+    }
+}
+
+/// A generator transforming the output of another generator
+///
+/// Returned by [`Generator::map_output`].
+#[must_use = "generators do nothing unless you `.start()` or `.resume(…)` them"]
+pub struct MapOutput<G, Func> {
+    generator: G,
+    f: Func,
+}
+
+impl<G, Func> MapOutput<G, Func> {
+    pub(crate) fn new(generator: G, f: Func) -> Self {
+        Self { generator, f }
+    }
+
+    /// Returns the underlying generator
+    pub fn into_inner(self) -> G {
+        self.generator
+    }
+}
+
+impl<G, Func, O2> MapOutput<G, Func>
+where
+    G: Generator,
+    Func: FnMut(G::Output) -> O2,
+{
+    fn map(&mut self, state: GnState<G::Yield, G::Output>) -> GnState<G::Yield, O2> {
+        match state {
+            GnState::Suspended(value) => GnState::Suspended(value),
+            GnState::Completed(value) => GnState::Completed((self.f)(value)),
+        }
+    }
+}
+
+impl<G, Func, O2> Generator for MapOutput<G, Func>
+where
+    G: Generator,
+    Func: FnMut(G::Output) -> O2,
+{
+    type Yield = G::Yield;
+    type Resume = G::Resume;
+    type Output = O2;
+
+    fn try_start(&mut self) -> Result<GnState<G::Yield, O2>, GnError> {
+        let state = self.generator.try_start()?;
+        Ok(self.map(state))
+    }
+
+    fn try_start_with(&mut self, value: G::Resume) -> Result<GnState<G::Yield, O2>, GnError> {
+        let state = self.generator.try_start_with(value)?;
+        Ok(self.map(state))
+    }
+
+    fn try_resume(&mut self, value: G::Resume) -> Result<GnState<G::Yield, O2>, GnError> {
+        let state = self.generator.try_resume(value)?;
+        Ok(self.map(state))
+    }
+
+    fn started(&self) -> bool {
+        self.generator.started()
+    }
+
+    fn is_completed(&self) -> bool {
+        self.generator.is_completed()
+    }
+}
+
+/// A generator filtering and transforming the values yielded by another generator
+///
+/// Returned by [`Generator::filter_map_yield`].
+#[must_use = "generators do nothing unless you `.start()` or `.resume(…)` them"]
+pub struct FilterMapYield<G, Func> {
+    generator: G,
+    f: Func,
+}
+
+impl<G, Func> FilterMapYield<G, Func> {
+    pub(crate) fn new(generator: G, f: Func) -> Self {
+        Self { generator, f }
+    }
+
+    /// Returns the underlying generator
+    pub fn into_inner(self) -> G {
+        self.generator
+    }
+}
+
+impl<G, Func, Y2> FilterMapYield<G, Func>
+where
+    G: Generator,
+    G::Resume: Default,
+    Func: FnMut(G::Yield) -> Option<Y2>,
+{
+    fn filter(
+        &mut self,
+        mut state: GnState<G::Yield, G::Output>,
+    ) -> Result<GnState<Y2, G::Output>, GnError> {
+        loop {
+            match state {
+                GnState::Suspended(value) => match (self.f)(value) {
+                    Some(value) => return Ok(GnState::Suspended(value)),
+                    None => state = self.generator.try_resume(G::Resume::default())?,
+                },
+                GnState::Completed(value) => return Ok(GnState::Completed(value)),
+            }
+        }
+    }
+}
+
+impl<G, Func, Y2> Generator for FilterMapYield<G, Func>
+where
+    G: Generator,
+    G::Resume: Default,
+    Func: FnMut(G::Yield) -> Option<Y2>,
+{
+    type Yield = Y2;
+    type Resume = G::Resume;
+    type Output = G::Output;
+
+    fn try_start(&mut self) -> Result<GnState<Y2, G::Output>, GnError> {
+        let state = self.generator.try_start()?;
+        self.filter(state)
+    }
+
+    fn try_start_with(&mut self, value: G::Resume) -> Result<GnState<Y2, G::Output>, GnError> {
+        let state = self.generator.try_start_with(value)?;
+        self.filter(state)
+    }
+
+    fn try_resume(&mut self, value: G::Resume) -> Result<GnState<Y2, G::Output>, GnError> {
+        let state = self.generator.try_resume(value)?;
+        self.filter(state)
+    }
+
+    fn started(&self) -> bool {
+        self.generator.started()
+    }
+
+    fn is_completed(&self) -> bool {
+        self.generator.is_completed()
+    }
+}
+
+/// A generator running a second generator built from the output of a first one
+///
+/// Returned by [`Generator::then`].
+#[must_use = "generators do nothing unless you `.start()` or `.resume(…)` them"]
+pub struct Then<G1, Func, G2> {
+    state: ThenState<G1, Func, G2>,
+}
+
+enum ThenState<G1, Func, G2> {
+    First(G1, Option<Func>),
+    Second(G2),
+}
+
+impl<G1, Func, G2> Then<G1, Func, G2> {
+    pub(crate) fn new(generator: G1, f: Func) -> Self {
+        Self {
+            state: ThenState::First(generator, Some(f)),
+        }
+    }
+}
+
+impl<G1, Func, G2> Then<G1, Func, G2>
+where
+    G1: Generator,
+    Func: FnOnce(G1::Output) -> G2,
+    G2: Generator<Yield = G1::Yield, Resume = G1::Resume>,
+{
+    fn chain(
+        &mut self,
+        state: GnState<G1::Yield, G1::Output>,
+    ) -> Result<GnState<G1::Yield, G2::Output>, GnError> {
+        match state {
+            GnState::Suspended(value) => Ok(GnState::Suspended(value)),
+            GnState::Completed(value) => {
+                let ThenState::First(_, f) = &mut self.state else {
+                    unreachable!("the first generator completed twice")
+                };
+                let f = f.take().expect("taken only once");
+                self.state = ThenState::Second(f(value));
+
+                let ThenState::Second(second) = &mut self.state else {
+                    unreachable!()
+                };
+                second.try_start()
+            }
+        }
+    }
+}
+
+impl<G1, Func, G2> Generator for Then<G1, Func, G2>
+where
+    G1: Generator,
+    Func: FnOnce(G1::Output) -> G2,
+    G2: Generator<Yield = G1::Yield, Resume = G1::Resume>,
+{
+    type Yield = G1::Yield;
+    type Resume = G1::Resume;
+    type Output = G2::Output;
+
+    fn try_start(&mut self) -> Result<GnState<G1::Yield, G2::Output>, GnError> {
+        match &mut self.state {
+            ThenState::First(first, _) => {
+                let state = first.try_start()?;
+                self.chain(state)
+            }
+            ThenState::Second(_) => Err(GnError::AlreadyStarted),
+        }
+    }
+
+    fn try_start_with(
+        &mut self,
+        value: G1::Resume,
+    ) -> Result<GnState<G1::Yield, G2::Output>, GnError> {
+        match &mut self.state {
+            ThenState::First(first, _) => {
+                let state = first.try_start_with(value)?;
+                self.chain(state)
+            }
+            ThenState::Second(_) => Err(GnError::AlreadyStarted),
+        }
+    }
+
+    fn try_resume(&mut self, value: G1::Resume) -> Result<GnState<G1::Yield, G2::Output>, GnError> {
+        match &mut self.state {
+            ThenState::First(first, _) => {
+                let state = first.try_resume(value)?;
+                self.chain(state)
+            }
+            ThenState::Second(second) => second.try_resume(value),
+        }
+    }
+
+    fn started(&self) -> bool {
+        match &self.state {
+            ThenState::First(first, _) => first.started(),
+            ThenState::Second(_) => true,
+        }
+    }
+
+    fn is_completed(&self) -> bool {
+        match &self.state {
+            ThenState::First(..) => false,
+            ThenState::Second(second) => second.is_completed(),
+        }
+    }
+}
+
+/// A generator feeding the values yielded by an upstream generator into a downstream generator
+///
+/// Returned by [`Generator::pipe`].
+///
+/// - Starting the pipe starts the downstream generator only, and the pipe yields the first value
+///   it yields. A start value is discarded, as the upstream generator is not running yet.
+/// - Resuming the pipe resumes the upstream generator, and the downstream generator is resumed
+///   with the value it yields. The first resume starts the upstream generator instead, passing
+///   the resume value as its [start value](crate::Co::take_start_value).
+/// - The values yielded by the downstream generator are yielded by the pipe.
+///
+/// The pipe completes as soon as any of the two generators completes, and its output tells which
+/// one did. The other generator is left untouched, and can be retrieved using
+/// [`into_inner`](Self::into_inner) to be driven further or to be dropped.
+#[must_use = "generators do nothing unless you `.start()` or `.resume(…)` them"]
+pub struct Pipe<G1, G2> {
+    upstream: G1,
+    downstream: G2,
+    completed: bool,
+}
+
+/// The output of a [`Pipe`], holding the output of the generator which completed first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipeOutput<O1, O2> {
+    /// The upstream generator completed first
+    Upstream(O1),
+    /// The downstream generator completed first
+    Downstream(O2),
+}
+
+impl<G1, G2> Pipe<G1, G2> {
+    pub(crate) fn new(upstream: G1, downstream: G2) -> Self {
+        Self {
+            upstream,
+            downstream,
+            completed: false,
+        }
+    }
+
+    /// Returns the upstream and downstream generators
+    pub fn into_inner(self) -> (G1, G2) {
+        (self.upstream, self.downstream)
+    }
+}
+
+impl<G1, G2> Pipe<G1, G2>
+where
+    G1: Generator,
+    G2: Generator<Resume = G1::Yield>,
+{
+    fn start_downstream(
+        &mut self,
+    ) -> Result<GnState<G2::Yield, <Self as Generator>::Output>, GnError> {
+        match self.downstream.try_start()? {
+            GnState::Suspended(value) => Ok(GnState::Suspended(value)),
+            GnState::Completed(output) => {
+                self.completed = true;
+                Ok(GnState::Completed(PipeOutput::Downstream(output)))
+            }
+        }
+    }
+
+    fn forward(
+        &mut self,
+        state: Result<GnState<G1::Yield, G1::Output>, GnError>,
+    ) -> Result<GnState<G2::Yield, <Self as Generator>::Output>, GnError> {
+        let state = match state? {
+            GnState::Suspended(value) => self.downstream.try_resume(value)?,
+            GnState::Completed(output) => {
+                self.completed = true;
+                return Ok(GnState::Completed(PipeOutput::Upstream(output)));
+            }
+        };
+
+        match state {
+            GnState::Suspended(value) => Ok(GnState::Suspended(value)),
+            GnState::Completed(output) => {
+                self.completed = true;
+                Ok(GnState::Completed(PipeOutput::Downstream(output)))
+            }
+        }
+    }
+}
+
+impl<G1, G2> Generator for Pipe<G1, G2>
+where
+    G1: Generator,
+    G2: Generator<Resume = G1::Yield>,
+{
+    type Yield = G2::Yield;
+    type Resume = G1::Resume;
+    type Output = PipeOutput<G1::Output, G2::Output>;
+
+    fn try_start(&mut self) -> Result<GnState<Self::Yield, Self::Output>, GnError> {
+        self.start_downstream()
+    }
+
+    fn try_start_with(
+        &mut self,
+        _: G1::Resume,
+    ) -> Result<GnState<Self::Yield, Self::Output>, GnError> {
+        self.start_downstream()
+    }
+
+    fn try_resume(
+        &mut self,
+        value: G1::Resume,
+    ) -> Result<GnState<Self::Yield, Self::Output>, GnError> {
+        if self.completed {
+            return Err(GnError::Completed);
+        }
+
+        if !self.downstream.started() {
+            return Err(GnError::NotStarted);
+        }
+
+        let state = if self.upstream.started() {
+            self.upstream.try_resume(value)
+        } else {
+            self.upstream.try_start_with(value)
+        };
+
+        self.forward(state)
+    }
+
+    fn started(&self) -> bool {
+        self.downstream.started()
+    }
+
+    fn is_completed(&self) -> bool {
+        self.completed
+    }
+}
@@ -0,0 +1,230 @@
+//! Driving generators using handlers
+//!
+//! A [`Handler`] computes the resume value for each value yielded by a generator. Closures
+//! taking the yielded value and returning the resume value are handlers.
+//!
+//! ```
+//! use genoise::local::{Co, Gn};
+//! use genoise::Generator as _;
+//!
+//! async fn sum(mut co: Co<'_, u32, u32>) -> u32 {
+//!     co.suspend(1).await + co.suspend(2).await
+//! }
+//!
+//! assert_eq!(Gn::new(sum).run_with(|value| value * 10), 30);
+//! ```
+//!
+//! A [`Driver`] is used to drive the generator step by step instead.
+//!
+//! ```
+//! use genoise::local::{Co, Gn};
+//! use genoise::Generator as _;
+//!
+//! async fn countdown(mut co: Co<'_, u32, ()>) -> &'static str {
+//!     for i in (1..=5).rev() {
+//!         co.suspend(i).await;
+//!     }
+//!     "liftoff"
+//! }
+//!
+//! let mut driver = Gn::new(countdown).drive(|_| ());
+//! assert_eq!(driver.run_until(|&i| i == 3), None);
+//! assert_eq!(driver.pending(), Some(&3));
+//! assert_eq!(driver.step_n(2), None);
+//! assert_eq!(driver.pending(), Some(&1));
+//! assert_eq!(driver.step_n(2), Some("liftoff"));
+//! ```
+//!
+//! Generators may also be driven from async code using [`drive_async`], where the handler
+//! returns a future resolving to the resume value.
+
+use core::future::Future;
+
+use crate::{Generator, GeneratorFlavor, Gn, GnError, GnState};
+
+/// Computes the resume value for each value yielded by a generator
+pub trait Handler<Y, R> {
+    /// Handles a value yielded by the generator, returning the value to resume it with
+    fn handle(&mut self, yielded: Y) -> R;
+}
+
+impl<Y, R, Func> Handler<Y, R> for Func
+where
+    Func: FnMut(Y) -> R,
+{
+    fn handle(&mut self, yielded: Y) -> R {
+        self(yielded)
+    }
+}
+
+/// Drives a generator step by step using a [`Handler`]
+///
+/// Returned by [`Generator::drive`].
+///
+/// A step is either starting the generator, or handling the pending yielded value and resuming
+/// the generator with the result.
+pub struct Driver<G: Generator, H> {
+    generator: G,
+    handler: H,
+    pending: Option<G::Yield>,
+}
+
+impl<G, H> Driver<G, H>
+where
+    G: Generator,
+    H: Handler<G::Yield, G::Resume>,
+{
+    /// Creates a new driver from a generator and a handler
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator was already started.
+    #[track_caller]
+    pub fn new(generator: G, handler: H) -> Self {
+        assert!(!generator.started(), "{}", GnError::AlreadyStarted);
+
+        Self {
+            generator,
+            handler,
+            pending: None,
+        }
+    }
+
+    /// Returns the last value yielded by the generator, not yet handled
+    pub fn pending(&self) -> Option<&G::Yield> {
+        self.pending.as_ref()
+    }
+
+    /// Executes one step, returning the output of the generator if it completed
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator already completed.
+    #[track_caller]
+    pub fn step(&mut self) -> Option<G::Output> {
+        let state = match self.pending.take() {
+            Some(yielded) => {
+                let value = self.handler.handle(yielded);
+                self.generator.resume(value)
+            }
+            None if self.generator.started() => panic!("{}", GnError::Completed),
+            None => self.generator.start(),
+        };
+
+        match state {
+            GnState::Suspended(yielded) => {
+                self.pending = Some(yielded);
+                None
+            }
+            GnState::Completed(output) => Some(output),
+        }
+    }
+
+    /// Executes at most `n` steps, returning the output of the generator if it completed
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator already completed.
+    #[track_caller]
+    pub fn step_n(&mut self, n: usize) -> Option<G::Output> {
+        for _ in 0..n {
+            if let Some(output) = self.step() {
+                return Some(output);
+            }
+        }
+
+        None
+    }
+
+    /// Executes steps until the generator yields a value matching the predicate, or completes
+    ///
+    /// The matching value is left [pending](Self::pending), and is handled on the next step.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator already completed.
+    #[track_caller]
+    pub fn run_until<P>(&mut self, mut predicate: P) -> Option<G::Output>
+    where
+        P: FnMut(&G::Yield) -> bool,
+    {
+        loop {
+            if let Some(output) = self.step() {
+                return Some(output);
+            }
+
+            if matches!(&self.pending, Some(yielded) if predicate(yielded)) {
+                return None;
+            }
+        }
+    }
+
+    /// Executes steps until the generator completes, returning its output
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator already completed.
+    #[track_caller]
+    pub fn run(mut self) -> G::Output {
+        loop {
+            if let Some(output) = self.step() {
+                return output;
+            }
+        }
+    }
+
+    /// Returns the generator and the handler
+    ///
+    /// The [pending](Self::pending) value, if any, is dropped.
+    pub fn into_inner(self) -> (G, H) {
+        (self.generator, self.handler)
+    }
+}
+
+/// Drives a generator until completion using an async handler, returning its output
+///
+/// For each yielded value, the future returned by the handler is awaited, and the generator is
+/// resumed with its output. The generator itself is executed synchronously, so it can't await
+/// anything else than [`Co::suspend`](crate::Co::suspend).
+///
+/// # Panics
+///
+/// Panics on generator misuse, for instance if it was already started.
+pub async fn drive_async<G, H, Fut>(mut generator: G, mut handler: H) -> G::Output
+where
+    G: Generator,
+    H: FnMut(G::Yield) -> Fut,
+    Fut: Future<Output = G::Resume>,
+{
+    let mut state = generator.start();
+
+    loop {
+        let yielded = match state {
+            GnState::Suspended(yielded) => yielded,
+            GnState::Completed(output) => return output,
+        };
+
+        let value = handler(yielded).await;
+        state = generator.resume(value);
+    }
+}
+
+impl<'gen, 'slot, Y, R, O, F> Gn<'gen, 'slot, Y, R, O, F>
+where
+    F: GeneratorFlavor,
+{
+    /// Drives the generator until completion using an async handler, returning its output
+    ///
+    /// See [`drive_async`].
+    ///
+    /// # Panics
+    ///
+    /// Panics on misuse, for instance if the generator was already started.
+    pub async fn drive_async<H, Fut>(self, handler: H) -> O
+    where
+        H: FnMut(Y) -> Fut,
+        Fut: Future<Output = R>,
+    {
+        drive_async(self, handler).await
+    }
+}
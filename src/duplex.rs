@@ -0,0 +1,200 @@
+//! Connecting two generators to each other
+//!
+//! Each generator yields a [`Turn`], either sending a message to its peer or waiting for one, and
+//! is resumed with the message sent by its peer, if any. [`run`] drives both generators until
+//! completion.
+//!
+//! ```
+//! use genoise::duplex::{self, Message, Turn};
+//! use genoise::local::{Co, Gn};
+//!
+//! async fn ping(mut co: Co<'_, Turn<&'static str>, Option<&'static str>>) -> usize {
+//!     let mut count = 0;
+//!     while co.suspend(Turn::Send("ping")).await == Some("pong") {
+//!         count += 1;
+//!     }
+//!     count
+//! }
+//!
+//! async fn pong(mut co: Co<'_, Turn<&'static str>, Option<&'static str>>) {
+//!     co.suspend(Turn::Waiting).await;
+//!     co.suspend(Turn::Send("pong")).await;
+//!     co.suspend(Turn::Send("pong")).await;
+//!     co.suspend(Turn::Send("bye")).await;
+//! }
+//!
+//! let mut log = Vec::new();
+//! let outputs = duplex::run(Gn::new(ping), Gn::new(pong), |message| match message {
+//!     Message::Initiator(message) => log.push(format!("-> {message}")),
+//!     Message::Responder(message) => log.push(format!("<- {message}")),
+//! });
+//!
+//! assert_eq!(outputs, Ok((2, ())));
+//! assert_eq!(log, ["-> ping", "<- pong", "-> ping", "<- pong", "-> ping", "<- bye"]);
+//! ```
+
+use core::fmt;
+
+use crate::{Generator, GnError, GnState};
+
+/// The value yielded by a generator connected to a peer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn<M> {
+    /// The generator is waiting for a message from its peer
+    Waiting,
+    /// The generator sends a message to its peer
+    Send(M),
+}
+
+/// A message exchanged between two generators, as reported by [`run`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Message<'m, A, B> {
+    /// The message was sent by the initiator
+    Initiator(&'m A),
+    /// The message was sent by the responder
+    Responder(&'m B),
+}
+
+/// Error returned by [`run`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DuplexError {
+    /// A generator is waiting for a message which will never be sent
+    Deadlock,
+    /// The responder sent a message before receiving any
+    ResponderSentFirst,
+    /// A generator sent a message to its peer, which already completed
+    PeerCompleted,
+    /// One of the generators was misused
+    Generator(GnError),
+}
+
+impl From<GnError> for DuplexError {
+    fn from(error: GnError) -> Self {
+        Self::Generator(error)
+    }
+}
+
+impl fmt::Display for DuplexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DuplexError::Deadlock => write!(
+                f,
+                "generator is waiting for a message which will never be sent"
+            ),
+            DuplexError::ResponderSentFirst => {
+                write!(f, "responder sent a message before receiving any")
+            }
+            DuplexError::PeerCompleted => {
+                write!(
+                    f,
+                    "generator sent a message to its peer, which already completed"
+                )
+            }
+            DuplexError::Generator(error) => error.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DuplexError {}
+
+/// Runs two generators connected to each other until both complete, returning their outputs
+///
+/// The initiator is started first, then the responder, which is expected to wait for the first
+/// message. From there, the responder and the initiator are resumed in turn with the message
+/// sent by their peer, or `None` if their peer did not send any. Each message is reported to
+/// `on_message` when sent.
+///
+/// Once a generator completes, its peer is resumed with `None` until it completes as well. A
+/// generator waiting for a message while its peer is waiting too, or while its peer completed,
+/// is a deadlock. A generator sending a message to its completed peer is an error as well, as the
+/// message can't be delivered.
+pub fn run<A, B, Ma, Mb, Func>(
+    mut initiator: A,
+    mut responder: B,
+    mut on_message: Func,
+) -> Result<(A::Output, B::Output), DuplexError>
+where
+    A: Generator<Yield = Turn<Ma>, Resume = Option<Mb>>,
+    B: Generator<Yield = Turn<Mb>, Resume = Option<Ma>>,
+    Func: FnMut(Message<'_, Ma, Mb>),
+{
+    let mut initiator_output = None;
+    let mut responder_output = None;
+
+    let (mut initiator_message, mut initiator_waiting) =
+        receive(initiator.try_start()?, &mut initiator_output, |message| {
+            on_message(Message::Initiator(message))
+        });
+
+    let mut responder_waiting = match responder.try_start()? {
+        GnState::Suspended(Turn::Waiting) => true,
+        GnState::Suspended(Turn::Send(_)) => return Err(DuplexError::ResponderSentFirst),
+        GnState::Completed(output) => {
+            responder_output = Some(output);
+            false
+        }
+    };
+    let mut responder_message = None;
+
+    loop {
+        if responder_output.is_none() {
+            if initiator_message.is_none() && responder_waiting {
+                return Err(DuplexError::Deadlock);
+            }
+
+            (responder_message, responder_waiting) = receive(
+                responder.try_resume(initiator_message.take())?,
+                &mut responder_output,
+                |message| on_message(Message::Responder(message)),
+            );
+        } else if initiator_message.is_some() {
+            return Err(DuplexError::PeerCompleted);
+        } else if initiator_output.is_some() {
+            break;
+        }
+
+        if initiator_output.is_none() {
+            if responder_message.is_none() && initiator_waiting {
+                return Err(DuplexError::Deadlock);
+            }
+
+            (initiator_message, initiator_waiting) = receive(
+                initiator.try_resume(responder_message.take())?,
+                &mut initiator_output,
+                |message| on_message(Message::Initiator(message)),
+            );
+        } else if responder_message.is_some() {
+            return Err(DuplexError::PeerCompleted);
+        } else if responder_output.is_some() {
+            break;
+        }
+    }
+
+    match (initiator_output, responder_output) {
+        (Some(initiator_output), Some(responder_output)) => {
+            Ok((initiator_output, responder_output))
+        }
+        _ => unreachable!("both generators completed"),
+    }
+}
+
+/// Returns the message sent in a turn, and whether the generator is waiting for a message
+fn receive<M, O>(
+    state: GnState<Turn<M>, O>,
+    output: &mut Option<O>,
+    on_message: impl FnOnce(&M),
+) -> (Option<M>, bool) {
+    match state {
+        GnState::Suspended(Turn::Send(message)) => {
+            on_message(&message);
+            (Some(message), false)
+        }
+        GnState::Suspended(Turn::Waiting) => (None, true),
+        GnState::Completed(value) => {
+            *output = Some(value);
+            (None, false)
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Typed effects, where each request determines the type of its response
+//!
+//! A generator yielding [`Request`]s and resumed with [`Response`]s performs effects using
+//! [`Co::perform`], and gets back the response type associated to the effect by the [`Effect`]
+//! trait. On the caller side, a [`Dispatcher`] routes each request to the handler registered for
+//! its effect.
+//!
+//! ```
+//! use genoise::effect::{Dispatcher, Effect, Request, Response};
+//! use genoise::local::{Co, Gn};
+//! use genoise::Generator as _;
+//!
+//! struct ReadLine;
+//!
+//! impl Effect for ReadLine {
+//!     type Response = String;
+//! }
+//!
+//! struct Add(u32, u32);
+//!
+//! impl Effect for Add {
+//!     type Response = u32;
+//! }
+//!
+//! async fn program(mut co: Co<'_, Request, Response>) -> u32 {
+//!     let line = co.perform(ReadLine).await;
+//!     let value = line.parse().unwrap();
+//!     co.perform(Add(value, 1)).await
+//! }
+//!
+//! let dispatcher = Dispatcher::new()
+//!     .on(|ReadLine| "41".to_owned())
+//!     .on(|Add(lhs, rhs)| lhs + rhs);
+//!
+//! assert_eq!(Gn::new(program).run_with(dispatcher), 42);
+//! ```
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::any::{type_name, Any, TypeId};
+use core::fmt;
+
+use crate::drive::Handler;
+use crate::{Co, GeneratorFlavor};
+
+/// A request performed by a generator, associated to the type of its response
+pub trait Effect: 'static {
+    /// The type of the response to this effect
+    type Response: 'static;
+}
+
+/// A type-erased [`Effect`]
+pub struct Request {
+    effect: Box<dyn Any>,
+    name: &'static str,
+}
+
+impl Request {
+    /// Erases the type of an effect
+    pub fn new<E: Effect>(effect: E) -> Self {
+        Self {
+            effect: Box::new(effect),
+            name: type_name::<E>(),
+        }
+    }
+
+    /// Returns whether the request holds an effect of type `E`
+    pub fn is<E: Effect>(&self) -> bool {
+        self.effect.is::<E>()
+    }
+
+    /// Returns the [`TypeId`] of the effect held by the request
+    pub fn effect_type_id(&self) -> TypeId {
+        (*self.effect).type_id()
+    }
+
+    /// Handles the request using `f` if it holds an effect of type `E`
+    ///
+    /// The request is given back if it holds an effect of another type.
+    pub fn handle<E, Func>(self, f: Func) -> Result<Response, Self>
+    where
+        E: Effect,
+        Func: FnOnce(E) -> E::Response,
+    {
+        match self.effect.downcast::<E>() {
+            Ok(effect) => Ok(Response::new::<E>(f(*effect))),
+            Err(effect) => Err(Self {
+                effect,
+                name: self.name,
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for Request {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Request").field(&self.name).finish()
+    }
+}
+
+/// A type-erased response to an [`Effect`]
+pub struct Response {
+    response: Box<dyn Any>,
+    effect: TypeId,
+    name: &'static str,
+}
+
+impl Response {
+    /// Erases the type of a response to an effect of type `E`
+    pub fn new<E: Effect>(response: E::Response) -> Self {
+        Self {
+            response: Box::new(response),
+            effect: TypeId::of::<E>(),
+            name: type_name::<E>(),
+        }
+    }
+}
+
+impl fmt::Debug for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Response").field(&self.name).finish()
+    }
+}
+
+impl<'slot, F> Co<'slot, Request, Response, F>
+where
+    F: GeneratorFlavor,
+{
+    /// Suspends the execution of the generator, performing an effect
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator is resumed with a response to another effect.
+    pub async fn perform<E: Effect>(&mut self, effect: E) -> E::Response {
+        let response = self.suspend(Request::new(effect)).await;
+
+        assert!(
+            response.effect == TypeId::of::<E>(),
+            "expected a response to `{}`, got a response to `{}`",
+            type_name::<E>(),
+            response.name,
+        );
+
+        match response.response.downcast::<E::Response>() {
+            Ok(response) => *response,
+            Err(_) => unreachable!("the response type is determined by the effect type"),
+        }
+    }
+}
+
+type ErasedHandler<'h> = Box<dyn FnMut(Request) -> Result<Response, Request> + 'h>;
+
+/// A [`Handler`] routing each [`Request`] to the handler registered for its effect
+#[derive(Default)]
+pub struct Dispatcher<'h> {
+    handlers: Vec<(TypeId, ErasedHandler<'h>)>,
+}
+
+impl<'h> Dispatcher<'h> {
+    /// Creates a dispatcher without any handler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the handler for effects of type `E`, replacing the previous one if any
+    #[must_use]
+    pub fn on<E, Func>(mut self, mut f: Func) -> Self
+    where
+        E: Effect,
+        Func: FnMut(E) -> E::Response + 'h,
+    {
+        let handler: ErasedHandler<'h> =
+            Box::new(move |request: Request| request.handle::<E, _>(&mut f));
+
+        match self
+            .handlers
+            .iter_mut()
+            .find(|(id, _)| *id == TypeId::of::<E>())
+        {
+            Some((_, previous)) => *previous = handler,
+            None => self.handlers.push((TypeId::of::<E>(), handler)),
+        }
+
+        self
+    }
+
+    /// Handles a request, giving it back if no handler is registered for its effect
+    pub fn try_dispatch(&mut self, request: Request) -> Result<Response, Request> {
+        let effect = request.effect_type_id();
+
+        match self.handlers.iter_mut().find(|(id, _)| *id == effect) {
+            Some((_, handler)) => handler(request),
+            None => Err(request),
+        }
+    }
+
+    /// Handles a request
+    ///
+    /// # Panics
+    ///
+    /// Panics if no handler is registered for the effect of the request.
+    #[track_caller]
+    pub fn dispatch(&mut self, request: Request) -> Response {
+        match self.try_dispatch(request) {
+            Ok(response) => response,
+            Err(request) => panic!("no handler registered for `{}`", request.name),
+        }
+    }
+}
+
+impl<'h> Handler<Request, Response> for Dispatcher<'h> {
+    fn handle(&mut self, request: Request) -> Response {
+        self.dispatch(request)
+    }
+}
@@ -0,0 +1,319 @@
+//! Iterator adapters for generators
+
+use core::iter::FusedIterator;
+
+use crate::{CellSlot, GeneratorFlavor, Gn, GnState};
+
+/// An iterator over the values yielded by a generator, keeping its output
+///
+/// Returned by [`Gn::into_iter_with_output`].
+pub struct WithOutput<'gen, 'slot, Y, O, F>
+where
+    O: 'gen,
+    CellSlot<Y, (), F>: 'slot,
+    F: GeneratorFlavor,
+{
+    generator: Gn<'gen, 'slot, Y, (), O, F>,
+    output: Option<O>,
+}
+
+impl<'gen, 'slot, Y, O, F> WithOutput<'gen, 'slot, Y, O, F>
+where
+    F: GeneratorFlavor,
+{
+    /// Returns the output of the generator, once the iterator is exhausted
+    pub fn output(&self) -> Option<&O> {
+        self.output.as_ref()
+    }
+
+    /// Takes the output of the generator, once the iterator is exhausted
+    pub fn take_output(&mut self) -> Option<O> {
+        self.output.take()
+    }
+
+    /// Consumes the iterator, returning the output of the generator if it is exhausted
+    pub fn into_output(self) -> Option<O> {
+        self.output
+    }
+}
+
+impl<'gen, 'slot, Y, O, F> Iterator for WithOutput<'gen, 'slot, Y, O, F>
+where
+    F: GeneratorFlavor,
+{
+    type Item = Y;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.generator.is_completed() {
+            return None;
+        }
+
+        let state = if self.generator.started() {
+            self.generator.resume(())
+        } else {
+            self.generator.start()
+        };
+
+        match state {
+            GnState::Suspended(value) => Some(value),
+            GnState::Completed(output) => {
+                self.output = Some(output);
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.generator.yield_hint()
+    }
+}
+
+impl<'gen, 'slot, Y, O, F> FusedIterator for WithOutput<'gen, 'slot, Y, O, F> where
+    F: GeneratorFlavor
+{
+}
+
+impl<'gen, 'slot, Y, O, F> Gn<'gen, 'slot, Y, (), O, F>
+where
+    F: GeneratorFlavor,
+{
+    /// Turns this generator into an iterator over the yielded values, keeping its output
+    ///
+    /// The output can be retrieved using [`WithOutput::output`] once the iterator is exhausted.
+    pub fn into_iter_with_output(self) -> WithOutput<'gen, 'slot, Y, O, F> {
+        WithOutput {
+            generator: self,
+            output: None,
+        }
+    }
+
+    /// Collects all the yielded values, along with the output of the generator
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator already completed.
+    #[track_caller]
+    pub fn collect_with_output<C>(self) -> (C, O)
+    where
+        C: FromIterator<Y>,
+    {
+        assert!(!self.is_completed(), "generator already completed");
+
+        let mut iter = self.into_iter_with_output();
+        let collection = iter.by_ref().collect();
+        let output = iter.into_output().expect("iterator is exhausted");
+
+        (collection, output)
+    }
+}
+
+/// Provides the values used to resume a generator when iterating over it
+///
+/// See [`Gn::iter_with_policy`].
+pub trait ResumePolicy<Y, R> {
+    /// Returns the value used to resume the generator after `yielded` was yielded, or `None` to
+    /// stop iterating
+    fn resume_value(&mut self, yielded: &Y) -> Option<R>;
+}
+
+/// Resumes the generator with [`R::default()`](Default::default)
+///
+/// Returned by [`Gn::iter_resume_default`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResumeDefault;
+
+impl<Y, R: Default> ResumePolicy<Y, R> for ResumeDefault {
+    fn resume_value(&mut self, _: &Y) -> Option<R> {
+        Some(R::default())
+    }
+}
+
+/// Resumes the generator with a clone of a fixed value
+///
+/// Returned by [`Gn::iter_resume_cloned`].
+#[derive(Debug, Clone)]
+pub struct ResumeCloned<R>(pub R);
+
+impl<Y, R: Clone> ResumePolicy<Y, R> for ResumeCloned<R> {
+    fn resume_value(&mut self, _: &Y) -> Option<R> {
+        Some(self.0.clone())
+    }
+}
+
+/// Resumes the generator with the value computed by a closure from the yielded value
+///
+/// Returned by [`Gn::iter_resume_with`].
+#[derive(Debug, Clone)]
+pub struct ResumeWith<Func>(pub Func);
+
+impl<Y, R, Func> ResumePolicy<Y, R> for ResumeWith<Func>
+where
+    Func: FnMut(&Y) -> R,
+{
+    fn resume_value(&mut self, yielded: &Y) -> Option<R> {
+        Some((self.0)(yielded))
+    }
+}
+
+/// Resumes the generator with the successive items of an iterator, stopping when it is exhausted
+///
+/// Returned by [`Gn::feed`].
+#[derive(Debug, Clone)]
+pub struct Feed<I>(pub I);
+
+impl<Y, R, I> ResumePolicy<Y, R> for Feed<I>
+where
+    I: Iterator<Item = R>,
+{
+    fn resume_value(&mut self, _: &Y) -> Option<R> {
+        self.0.next()
+    }
+}
+
+/// An iterator over the values yielded by a generator, resuming it according to a
+/// [`ResumePolicy`]
+///
+/// Iteration stops when the generator completes, in which case its output is kept, or when the
+/// policy does not provide a resume value anymore, in which case the generator is left suspended.
+pub struct Resumed<'gen, 'slot, Y, R, O, F, P>
+where
+    O: 'gen,
+    CellSlot<Y, R, F>: 'slot,
+    F: GeneratorFlavor,
+{
+    generator: Gn<'gen, 'slot, Y, R, O, F>,
+    policy: P,
+    resume_value: Option<R>,
+    output: Option<O>,
+}
+
+impl<'gen, 'slot, Y, R, O, F, P> Resumed<'gen, 'slot, Y, R, O, F, P>
+where
+    F: GeneratorFlavor,
+{
+    /// Returns the output of the generator, once it completed
+    pub fn output(&self) -> Option<&O> {
+        self.output.as_ref()
+    }
+
+    /// Consumes the iterator, returning the output of the generator if it completed
+    pub fn into_output(self) -> Option<O> {
+        self.output
+    }
+
+    /// Consumes the iterator, returning the underlying generator along with the value the policy
+    /// computed to resume it, if any
+    ///
+    /// The resume value is computed as soon as a value is yielded, so it is handed back here
+    /// instead of being lost (e.g. an item already taken from the iterator of a [`Feed`]).
+    pub fn into_inner(self) -> (Gn<'gen, 'slot, Y, R, O, F>, Option<R>) {
+        (self.generator, self.resume_value)
+    }
+}
+
+impl<'gen, 'slot, Y, R, O, F, P> Iterator for Resumed<'gen, 'slot, Y, R, O, F, P>
+where
+    F: GeneratorFlavor,
+    P: ResumePolicy<Y, R>,
+{
+    type Item = Y;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.generator.is_completed() {
+            return None;
+        }
+
+        let state = if self.generator.started() {
+            let value = self.resume_value.take()?;
+            self.generator.resume(value)
+        } else {
+            self.generator.start()
+        };
+
+        match state {
+            GnState::Suspended(value) => {
+                self.resume_value = self.policy.resume_value(&value);
+                Some(value)
+            }
+            GnState::Completed(output) => {
+                self.output = Some(output);
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.generator.started() && self.resume_value.is_none() {
+            // The policy stopped the iteration
+            (0, Some(0))
+        } else {
+            self.generator.yield_hint()
+        }
+    }
+}
+
+impl<'gen, 'slot, Y, R, O, F, P> FusedIterator for Resumed<'gen, 'slot, Y, R, O, F, P>
+where
+    F: GeneratorFlavor,
+    P: ResumePolicy<Y, R>,
+{
+}
+
+impl<'gen, 'slot, Y, R, O, F> Gn<'gen, 'slot, Y, R, O, F>
+where
+    F: GeneratorFlavor,
+{
+    /// Turns this generator into an iterator over the yielded values, resuming it according to
+    /// the given policy
+    ///
+    /// The generator must not be started yet, otherwise the iterator is empty.
+    pub fn iter_with_policy<P>(self, policy: P) -> Resumed<'gen, 'slot, Y, R, O, F, P>
+    where
+        P: ResumePolicy<Y, R>,
+    {
+        Resumed {
+            generator: self,
+            policy,
+            resume_value: None,
+            output: None,
+        }
+    }
+
+    /// Turns this generator into an iterator, resuming it with [`R::default()`](Default::default)
+    pub fn iter_resume_default(self) -> Resumed<'gen, 'slot, Y, R, O, F, ResumeDefault>
+    where
+        R: Default,
+    {
+        self.iter_with_policy(ResumeDefault)
+    }
+
+    /// Turns this generator into an iterator, resuming it with a clone of `value`
+    pub fn iter_resume_cloned(self, value: R) -> Resumed<'gen, 'slot, Y, R, O, F, ResumeCloned<R>>
+    where
+        R: Clone,
+    {
+        self.iter_with_policy(ResumeCloned(value))
+    }
+
+    /// Turns this generator into an iterator, resuming it with the value returned by `f` for each
+    /// yielded value
+    pub fn iter_resume_with<Func>(
+        self,
+        f: Func,
+    ) -> Resumed<'gen, 'slot, Y, R, O, F, ResumeWith<Func>>
+    where
+        Func: FnMut(&Y) -> R,
+    {
+        self.iter_with_policy(ResumeWith(f))
+    }
+
+    /// Turns this generator into an iterator, resuming it with the successive items of `iter`
+    ///
+    /// Iteration stops once `iter` is exhausted, even if the generator did not complete.
+    pub fn feed<I>(self, iter: I) -> Resumed<'gen, 'slot, Y, R, O, F, Feed<I::IntoIter>>
+    where
+        I: IntoIterator<Item = R>,
+    {
+        self.iter_with_policy(Feed(iter.into_iter()))
+    }
+}
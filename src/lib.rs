@@ -1,4 +1,12 @@
 #![doc = include_str!("../README.md")]
+// Heap flavors are not compiled without the `alloc` feature, so the README links to them online
+#![cfg_attr(
+    not(feature = "alloc"),
+    doc = "",
+    doc = "[`local::Gn`]: https://docs.rs/genoise/latest/genoise/local/type.Gn.html",
+    doc = "[`send::Gn`]: https://docs.rs/genoise/latest/genoise/send/type.Gn.html",
+    doc = "[`sync::Gn`]: https://docs.rs/genoise/latest/genoise/sync/type.Gn.html"
+)]
 #![warn(clippy::undocumented_unsafe_blocks)]
 #![warn(clippy::multiple_unsafe_ops_per_block)]
 #![warn(clippy::semicolon_outside_block)]
@@ -9,14 +17,38 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 use core::fmt;
 use core::future::Future;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
 use core::ops::{Deref, DerefMut};
 use core::pin::Pin;
 use core::task::{Context, Poll, Waker};
 
+pub mod combinator;
+pub mod drive;
+pub mod duplex;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod effect;
+pub mod iter;
 pub mod local;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod pool;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod sched;
+pub mod send;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod session;
+pub mod stream;
 pub mod sync;
+pub mod typestate;
 
 #[macro_export]
 macro_rules! let_gen {
@@ -52,6 +84,24 @@ pub trait GeneratorFlavor {
     fn new_cell<T>(value: T) -> Self::Cell<T>;
 
     fn cell_replace<T>(cell: &Self::Cell<T>, other: T) -> T;
+
+    /// Fallible counterpart to [`cell_replace`](Self::cell_replace)
+    ///
+    /// When the cell can't be borrowed, `other` is handed back in the `Err` variant.
+    fn cell_try_replace<T>(cell: &Self::Cell<T>, other: T) -> Result<T, T> {
+        Ok(Self::cell_replace(cell, other))
+    }
+
+    /// Copies the value held by the cell, or returns `None` if the cell can't be borrowed
+    ///
+    /// The default implementation swaps `T::default()` in and writes the value back, so flavors
+    /// whose cells can be shared between threads must override it: otherwise, a concurrent read
+    /// may restore the placeholder instead of the value.
+    fn cell_try_get<T: Copy + Default>(cell: &Self::Cell<T>) -> Option<T> {
+        let value = Self::cell_try_replace(cell, T::default()).ok()?;
+        let _ = Self::cell_try_replace(cell, value);
+        Some(value)
+    }
 }
 
 pub trait StackFlavor: GeneratorFlavor {}
@@ -64,6 +114,10 @@ enum Slot<Y, R> {
     Empty,
     YieldValue(Y),
     ResumeValue(R),
+    /// Set by an `Interrupt` polled again without any resume value
+    Unresumed,
+    /// Set by the generator executor when cancelling the generator
+    Cancel,
 }
 
 impl<Y, R> Slot<Y, R> {
@@ -74,21 +128,29 @@ impl<Y, R> Slot<Y, R> {
             None
         }
     }
-
-    fn into_resume_value(self) -> Option<R> {
-        if let Self::ResumeValue(value) = self {
-            Some(value)
-        } else {
-            None
-        }
-    }
 }
 
-pub struct CellSlot<Y, R, F: GeneratorFlavor>(F::Cell<Slot<Y, R>>);
+/// Bounds on the remaining number of yields, as declared using [`Co::set_size_hint`]
+type SizeHint = (usize, Option<usize>);
+
+/// Outcome of an execution step, which may have been cancelled
+type CancellableStep<Y, O> = Result<GnState<Y, Result<O, Cancelled>>, GnError>;
+
+pub struct CellSlot<Y, R, F: GeneratorFlavor>(F::Cell<Slot<Y, R>>, F::Cell<SizeHint>);
 
 impl<Y, R, F: GeneratorFlavor> Default for CellSlot<Y, R, F> {
     fn default() -> Self {
-        Self(F::new_cell(Slot::Empty))
+        Self(F::new_cell(Slot::Empty), F::new_cell((0, None)))
+    }
+}
+
+impl<Y, R, F: GeneratorFlavor> CellSlot<Y, R, F> {
+    fn size_hint(&self) -> SizeHint {
+        F::cell_try_get(&self.1).unwrap_or((0, None))
+    }
+
+    fn set_size_hint(&self, hint: SizeHint) {
+        let _ = F::cell_try_replace(&self.1, hint);
     }
 }
 
@@ -131,6 +193,46 @@ where
         }
     }
 
+    /// Takes the value passed in by the caller when the generator is started using
+    /// [`Gn::start_with`]
+    ///
+    /// This must be called before suspending for the first time, otherwise the value is
+    /// discarded and `None` is returned.
+    pub fn take_start_value(&mut self) -> Option<R> {
+        match F::cell_try_replace(&self.slot.0, Slot::Empty) {
+            Ok(Slot::ResumeValue(value)) => Some(value),
+            Ok(other) => {
+                let _ = F::cell_try_replace(&self.slot.0, other);
+                None
+            }
+            Err(_) => None,
+        }
+    }
+
+    /// Declares bounds on the number of values this generator will yield from now on
+    ///
+    /// The bounds are decremented each time the generator suspends, and may be updated at any
+    /// time. They are reported by [`Gn::yield_hint`] and [`Iterator::size_hint`]. Like for
+    /// [`Iterator::size_hint`], a buggy implementation should not lead to memory safety
+    /// violations, but may cause the consumer to misbehave.
+    ///
+    /// Bounds known before the generator is started are declared using [`Gn::with_size_hint`].
+    pub fn set_size_hint(&mut self, lower: usize, upper: Option<usize>) {
+        self.slot.set_size_hint((lower, upper));
+    }
+
+    /// Suspends the execution of the generator, yielding an intermediate value and allowing
+    /// cancellation
+    ///
+    /// Resolves to `Err(Cancelled)` when the generator is cancelled using [`Gn::cancel`]. The
+    /// generator may then suspend further to yield some final cleanup values before completing.
+    ///
+    /// When a generator is cancelled while suspended by [`suspend`](Self::suspend) instead, the
+    /// inner future is simply dropped.
+    pub fn suspend_cancellable(&mut self, value: Y) -> CancellableInterrupt<'slot, Y, R, F> {
+        CancellableInterrupt(self.suspend(value))
+    }
+
     // TODO: write a test to see what happen when a lot of "suspend" are created but not awaited
     // The expectation is that we can change the order in which values are exchanged, but no value is lost unless
     // `Interrupt` is not polled at all.
@@ -157,12 +259,121 @@ where
             state = generator.resume(resume_value);
         }
     }
+
+    /// Executes another generator with different yield and resume types until completion,
+    /// retrieving its return value
+    ///
+    /// Values yielded by the generator are converted using `map_yield` before being yielded by this
+    /// controller, and resume values are converted back using `map_resume`.
+    pub async fn suspend_from_mapped<'gen, 'child, Y2, R2, O, F2, MY, MR>(
+        &mut self,
+        mut generator: Gn<'gen, 'child, Y2, R2, O, F2>,
+        mut map_yield: MY,
+        mut map_resume: MR,
+    ) -> O
+    where
+        F2: GeneratorFlavor,
+        MY: FnMut(Y2) -> Y,
+        MR: FnMut(R) -> R2,
+    {
+        let mut state = generator.start();
+
+        loop {
+            let resume_value = match state {
+                GnState::Suspended(yielded) => self.suspend(map_yield(yielded)).await,
+                GnState::Completed(returned) => break returned,
+            };
+
+            state = generator.resume(map_resume(resume_value));
+        }
+    }
+
+    /// Returns a view of this controller with different yield and resume types
+    ///
+    /// Unlike [`suspend_from_mapped`](Self::suspend_from_mapped), no child generator is involved.
+    pub fn map<Y2, R2, MY, MR>(
+        &mut self,
+        map_yield: MY,
+        map_resume: MR,
+    ) -> MappedCo<'_, 'slot, Y, R, F, Y2, R2, MY, MR>
+    where
+        MY: FnMut(Y2) -> Y,
+        MR: FnMut(R) -> R2,
+    {
+        MappedCo {
+            co: self,
+            map_yield,
+            map_resume,
+            _types: PhantomData,
+        }
+    }
+
+    /// Yields every item of an iterator, discarding the resume values
+    ///
+    /// This is the equivalent of calling [`suspend`](Self::suspend) in a `for` loop.
+    pub async fn suspend_iter<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Y>,
+    {
+        for item in iter {
+            self.suspend(item).await;
+        }
+    }
+
+    /// Yields every item of an iterator, collecting the resume values
+    pub async fn suspend_iter_collect<I, C>(&mut self, iter: I) -> C
+    where
+        I: IntoIterator<Item = Y>,
+        C: Default + Extend<R>,
+    {
+        let mut resume_values = C::default();
+
+        for item in iter {
+            let resume_value = self.suspend(item).await;
+            resume_values.extend(core::iter::once(resume_value));
+        }
+
+        resume_values
+    }
+}
+
+/// A view of a [`Co`] with different yield and resume types
+///
+/// Returned by [`Co::map`].
+pub struct MappedCo<'co, 'slot, Y, R, F, Y2, R2, MY, MR>
+where
+    F: GeneratorFlavor,
+    CellSlot<Y, R, F>: 'slot,
+{
+    co: &'co mut Co<'slot, Y, R, F>,
+    map_yield: MY,
+    map_resume: MR,
+    _types: PhantomData<fn(Y2) -> R2>,
+}
+
+impl<'co, 'slot, Y, R, F, Y2, R2, MY, MR> MappedCo<'co, 'slot, Y, R, F, Y2, R2, MY, MR>
+where
+    F: GeneratorFlavor,
+    Y: Unpin + 'slot,
+    R: 'slot,
+    MY: FnMut(Y2) -> Y,
+    MR: FnMut(R) -> R2,
+{
+    /// Suspends the execution of the generator, yielding an intermediate value
+    ///
+    /// See [`Co::suspend`].
+    pub async fn suspend(&mut self, value: Y2) -> R2 {
+        let resume_value = self.co.suspend((self.map_yield)(value)).await;
+        (self.map_resume)(resume_value)
+    }
 }
 
 /// Future type that resolves to the value passed in by the caller when [`Gn::resume`] is called and
 /// execution is resumed.
 ///
-/// This is the only future that may be polled by a [`Gn`].
+/// This is the only future that may suspend a [`Gn`]. Other futures may be `.await`ed inside a
+/// generator as long as they complete immediately (e.g. [`core::future::ready`]), otherwise
+/// [`GnError::ForeignFuture`] is reported.
 pub struct Interrupt<'slot, Y, R, F>
 where
     F: GeneratorFlavor,
@@ -172,6 +383,40 @@ where
     slot: F::SharedPtr<'slot, CellSlot<Y, R, F>>,
 }
 
+impl<'slot, Y, R, F> Interrupt<'slot, Y, R, F>
+where
+    F: GeneratorFlavor,
+{
+    fn poll_slot(&mut self, cancellable: bool) -> Poll<Result<R, Cancelled>> {
+        if let Some(yielded_value) = self.yielded_value.take() {
+            if let Err(slot) = F::cell_try_replace(&self.slot.0, Slot::YieldValue(yielded_value)) {
+                // Try again on next poll, the generator executor will report the error
+                self.yielded_value = slot.into_yield_value();
+            }
+
+            Poll::Pending
+        } else {
+            match F::cell_try_replace(&self.slot.0, Slot::Empty) {
+                Ok(Slot::ResumeValue(resume_value)) => Poll::Ready(Ok(resume_value)),
+                Ok(Slot::Cancel) if cancellable => Poll::Ready(Err(Cancelled)),
+                Ok(Slot::Empty) => {
+                    // The resume value is missing: stay pending, the generator executor will
+                    // report the protocol violation
+                    let _ = F::cell_try_replace(&self.slot.0, Slot::Unresumed);
+                    Poll::Pending
+                }
+                Ok(other) => {
+                    // Put back whatever was found, most likely a value yielded by another `Interrupt`
+                    // or a cancellation request to be handled by the generator executor
+                    let _ = F::cell_try_replace(&self.slot.0, other);
+                    Poll::Pending
+                }
+                Err(_) => Poll::Pending,
+            }
+        }
+    }
+}
+
 impl<'slot, Y, R, F> Future for Interrupt<'slot, Y, R, F>
 where
     Y: Unpin,
@@ -180,20 +425,49 @@ where
     type Output = R;
 
     fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
-        let this = self.get_mut();
-
-        if let Some(yielded_value) = this.yielded_value.take() {
-            F::cell_replace(&this.slot.0, Slot::YieldValue(yielded_value));
-            Poll::Pending
-        } else {
-            let resume_value = F::cell_replace(&this.slot.0, Slot::Empty)
-                .into_resume_value()
-                .expect("resume value set by generator executor");
-            Poll::Ready(resume_value)
+        match self.get_mut().poll_slot(false) {
+            Poll::Ready(Ok(resume_value)) => Poll::Ready(resume_value),
+            Poll::Ready(Err(Cancelled)) | Poll::Pending => Poll::Pending,
         }
     }
 }
 
+/// Future type returned by [`Co::suspend_cancellable`]
+///
+/// Resolves to the value passed in by the caller when [`Gn::resume`] is called, or to
+/// [`Cancelled`] when [`Gn::cancel`] is called.
+pub struct CancellableInterrupt<'slot, Y, R, F>(Interrupt<'slot, Y, R, F>)
+where
+    F: GeneratorFlavor,
+    CellSlot<Y, R, F>: 'slot;
+
+impl<'slot, Y, R, F> Future for CancellableInterrupt<'slot, Y, R, F>
+where
+    Y: Unpin,
+    F: GeneratorFlavor,
+{
+    type Output = Result<R, Cancelled>;
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
+        self.get_mut().0.poll_slot(true)
+    }
+}
+
+/// Returned to a generator cancelled using [`Gn::cancel`], and to the caller when the
+/// cancellation is effective
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "generator was cancelled")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for Cancelled {}
+
 /// The result of a generator execution.
 pub enum GnState<Y, O> {
     Suspended(Y),
@@ -213,6 +487,115 @@ where
     }
 }
 
+/// An error returned when a generator is misused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GnError {
+    /// The generator was resumed before being started
+    NotStarted,
+    /// The generator was started more than once
+    AlreadyStarted,
+    /// The generator was resumed after completion
+    Completed,
+    /// An [`Interrupt`] was polled again without any resume value (e.g. several interrupts were
+    /// polled concurrently)
+    SlotProtocol,
+    /// The memory slot used to exchange yield and resume values was already borrowed
+    AlreadyBorrowed,
+    /// The generator is waiting on a future other than [`Interrupt`]
+    ///
+    /// Generators are driven without any async runtime, so they can't wait for I/O, timers,
+    /// channels and so on. Only futures that complete immediately may be `.await`ed besides the
+    /// ones returned by [`Co::suspend`], unless the generator is driven from async code using
+    /// [`Gn::into_async`].
+    ForeignFuture,
+    /// The generator panicked during a previous execution step
+    Poisoned,
+    /// The generator was resumed while a previous execution step is still pending
+    ///
+    /// Only [`AsyncGn`](stream::AsyncGn) may be in such state.
+    StepInProgress,
+    /// A pending execution step was polled while none was started
+    ///
+    /// Only returned by [`AsyncGn::poll_step`](stream::AsyncGn::poll_step).
+    NoStepInProgress,
+}
+
+impl fmt::Display for GnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GnError::NotStarted => write!(f, "generator must be started before it can be resumed"),
+            GnError::AlreadyStarted => write!(f, "generator was already started"),
+            GnError::Completed => write!(f, "generator was resumed after completion"),
+            GnError::SlotProtocol => write!(
+                f,
+                "yield and resume values were not exchanged through the `Interrupt` as expected"
+            ),
+            GnError::AlreadyBorrowed => write!(f, "generator slot is already borrowed"),
+            GnError::ForeignFuture => write!(
+                f,
+                "generator is waiting on a future other than `Interrupt`; only `Co::suspend` may suspend a generator"
+            ),
+            GnError::Poisoned => write!(f, "generator panicked during a previous execution step"),
+            GnError::StepInProgress => {
+                write!(f, "generator was resumed while a previous execution step is still pending")
+            }
+            GnError::NoStepInProgress => write!(f, "generator has no pending execution step"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for GnError {}
+
+/// Error returned by [`Gn::start_catch_unwind`] and [`Gn::resume_catch_unwind`]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CatchUnwindError {
+    /// The generator was misused
+    Generator(GnError),
+    /// The generator panicked, the panic payload is provided
+    Panicked(std::boxed::Box<dyn core::any::Any + Send + 'static>),
+}
+
+#[cfg(feature = "std")]
+impl From<GnError> for CatchUnwindError {
+    fn from(error: GnError) -> Self {
+        Self::Generator(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for CatchUnwindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatchUnwindError::Generator(error) => error.fmt(f),
+            CatchUnwindError::Panicked(payload) => {
+                if let Some(message) = payload.downcast_ref::<&str>() {
+                    write!(f, "generator panicked: {message}")
+                } else if let Some(message) = payload.downcast_ref::<std::string::String>() {
+                    write!(f, "generator panicked: {message}")
+                } else {
+                    write!(f, "generator panicked")
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CatchUnwindError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CatchUnwindError::Generator(error) => Some(error),
+            CatchUnwindError::Panicked(_) => None,
+        }
+    }
+}
+
 /// A generator
 ///
 /// Generators control the flow of three types of data:
@@ -228,8 +611,11 @@ where
     F: GeneratorFlavor,
 {
     slot: F::SharedPtr<'slot, CellSlot<Y, R, F>>,
-    generator: Pin<F::UniquePtr<'gen, F::Fut<'gen, O>>>,
+    /// `None` once the generator completed, so that resources held by the future are freed early
+    generator: Option<Pin<F::UniquePtr<'gen, F::Fut<'gen, O>>>>,
     started: bool,
+    /// Set while the inner future is polled, and left as-is if polling panics
+    poisoned: bool,
 }
 
 impl<'gen, 'slot, Y, R, O, F: GeneratorFlavor> Gn<'gen, 'slot, Y, R, O, F> {
@@ -239,8 +625,9 @@ impl<'gen, 'slot, Y, R, O, F: GeneratorFlavor> Gn<'gen, 'slot, Y, R, O, F> {
     ) -> Self {
         Self {
             slot,
-            generator,
+            generator: Some(generator),
             started: false,
+            poisoned: false,
         }
     }
 
@@ -249,39 +636,523 @@ impl<'gen, 'slot, Y, R, O, F: GeneratorFlavor> Gn<'gen, 'slot, Y, R, O, F> {
         self.started
     }
 
+    /// Returns whether the generator completed or not
+    ///
+    /// Once completed, the inner future is dropped and the generator can't be resumed anymore.
+    pub fn is_completed(&self) -> bool {
+        self.generator.is_none()
+    }
+
+    /// Returns the bounds on the remaining number of yields
+    ///
+    /// Bounds are declared before starting using [`with_size_hint`](Self::with_size_hint), or by
+    /// the generator itself using [`Co::set_size_hint`], and default to `(0, None)`. Once
+    /// completed, `(0, Some(0))` is returned.
+    pub fn yield_hint(&self) -> (usize, Option<usize>) {
+        if self.is_completed() {
+            (0, Some(0))
+        } else {
+            self.slot.size_hint()
+        }
+    }
+
+    /// Declares bounds on the number of values this generator will yield
+    ///
+    /// This makes the bounds available before the generator is started, for instance when
+    /// [`Iterator::collect`] is called on it. The generator may still update them afterwards using
+    /// [`Co::set_size_hint`].
+    pub fn with_size_hint(self, lower: usize, upper: Option<usize>) -> Self {
+        self.slot.set_size_hint((lower, upper));
+        self
+    }
+
+    /// Returns whether the generator panicked during a previous execution step
+    ///
+    /// A poisoned generator can't be resumed anymore.
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
     /// Starts execution of the generator
     ///
     /// This method must be called exactly once before calling [`resume`](Self::resume).
+    ///
+    /// # Panics
+    ///
+    /// Panics on misuse, see [`try_start`](Self::try_start) for a non-panicking alternative.
+    #[track_caller]
     pub fn start(&mut self) -> GnState<Y, O> {
-        self.started = true;
-        self.step()
+        match self.try_start() {
+            Ok(state) => state,
+            Err(error) => panic!("{error}"),
+        }
     }
 
     /// Resumes execution of the generator, passing in a value
     ///
     /// [`start`](Self::start) must be called before resumption can happen.
+    ///
+    /// # Panics
+    ///
+    /// Panics on misuse, see [`try_resume`](Self::try_resume) for a non-panicking alternative.
+    #[track_caller]
     pub fn resume(&mut self, value: R) -> GnState<Y, O> {
-        assert!(
-            self.started,
-            "generator must be started before it can be resumed"
-        );
+        match self.try_resume(value) {
+            Ok(state) => state,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Starts execution of the generator, passing in a first value
+    ///
+    /// This is similar to [`start`](Self::start), but the generator may retrieve the value
+    /// using [`Co::take_start_value`] before suspending for the first time. This matches the
+    /// semantics of the coroutines available on Rust nightly.
+    ///
+    /// # Panics
+    ///
+    /// Panics on misuse, see [`try_start_with`](Self::try_start_with) for a non-panicking
+    /// alternative.
+    #[track_caller]
+    pub fn start_with(&mut self, value: R) -> GnState<Y, O> {
+        match self.try_start_with(value) {
+            Ok(state) => state,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Starts execution of the generator, returning an error on misuse
+    pub fn try_start(&mut self) -> Result<GnState<Y, O>, GnError> {
+        self.begin_start()?;
+        self.step()
+    }
 
-        F::cell_replace(&self.slot.0, Slot::ResumeValue(value));
+    /// Starts execution of the generator, passing in a first value and returning an error on misuse
+    pub fn try_start_with(&mut self, value: R) -> Result<GnState<Y, O>, GnError> {
+        self.begin_start_with(value)?;
+        self.step()
+    }
+
+    /// Resumes execution of the generator, passing in a value and returning an error on misuse
+    pub fn try_resume(&mut self, value: R) -> Result<GnState<Y, O>, GnError> {
+        self.begin_resume(value)?;
+        self.step()
+    }
 
+    /// Resumes execution of a generator known to be started and not completed
+    fn resume_started(&mut self, value: R) -> Result<GnState<Y, O>, GnError> {
+        self.write_resume_value(value)?;
         self.step()
     }
 
-    fn step(&mut self) -> GnState<Y, O> {
-        match execute_one_step(self.generator.as_mut()) {
-            None => {
-                let value = F::cell_replace(&self.slot.0, Slot::Empty)
-                    .into_yield_value()
-                    .expect("yielded value set by the `await`ed `Interrupt`");
-                GnState::Suspended(value)
+    /// Prepares the generator for its first execution step
+    pub(crate) fn begin_start(&mut self) -> Result<(), GnError> {
+        self.check_startable()?;
+        self.started = true;
+        Ok(())
+    }
+
+    /// Prepares the generator for its first execution step, passing in a first value
+    pub(crate) fn begin_start_with(&mut self, value: R) -> Result<(), GnError> {
+        self.check_startable()?;
+        self.write_resume_value(value)?;
+        self.started = true;
+        Ok(())
+    }
+
+    /// Prepares the generator for its next execution step, passing in a value
+    pub(crate) fn begin_resume(&mut self, value: R) -> Result<(), GnError> {
+        if self.poisoned {
+            return Err(GnError::Poisoned);
+        }
+
+        if !self.started {
+            return Err(GnError::NotStarted);
+        }
+
+        if self.is_completed() {
+            return Err(GnError::Completed);
+        }
+
+        self.write_resume_value(value)
+    }
+
+    fn check_startable(&self) -> Result<(), GnError> {
+        if self.poisoned {
+            return Err(GnError::Poisoned);
+        }
+
+        if self.started {
+            return Err(GnError::AlreadyStarted);
+        }
+
+        Ok(())
+    }
+
+    fn write_resume_value(&self, value: R) -> Result<(), GnError> {
+        F::cell_try_replace(&self.slot.0, Slot::ResumeValue(value))
+            .map(drop)
+            .map_err(|_| GnError::AlreadyBorrowed)
+    }
+
+    /// Cancels execution of the generator
+    ///
+    /// If the generator is suspended by [`Co::suspend_cancellable`], it is resumed with
+    /// [`Cancelled`] and may yield some final cleanup values: keep resuming it using
+    /// [`resume`](Self::resume) until completion in such case. Otherwise, the inner future is
+    /// dropped right away and `Completed(Err(Cancelled))` is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics on misuse, see [`try_cancel`](Self::try_cancel) for a non-panicking alternative.
+    #[track_caller]
+    pub fn cancel(&mut self) -> GnState<Y, Result<O, Cancelled>> {
+        match self.try_cancel() {
+            Ok(state) => state,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Cancels execution of the generator, returning an error on misuse
+    ///
+    /// See [`cancel`](Self::cancel).
+    pub fn try_cancel(&mut self) -> Result<GnState<Y, Result<O, Cancelled>>, GnError> {
+        if self.poisoned {
+            return Err(GnError::Poisoned);
+        }
+
+        if self.is_completed() {
+            return Err(GnError::Completed);
+        }
+
+        if !self.started {
+            self.started = true;
+            self.generator = None;
+            return Ok(GnState::Completed(Err(Cancelled)));
+        }
+
+        F::cell_try_replace(&self.slot.0, Slot::Cancel).map_err(|_| GnError::AlreadyBorrowed)?;
+
+        without_waker(|cx| self.poll_step_cancellable(cx))
+    }
+
+    fn step(&mut self) -> Result<GnState<Y, O>, GnError> {
+        without_waker(|cx| self.poll_step(cx))
+    }
+
+    /// Executes one step of the generator, prepared beforehand using one of the `begin_*` methods
+    ///
+    /// `Poll::Pending` is returned when the generator is waiting on a future other than
+    /// [`Interrupt`], in which case this method should be called again once the waker is woken up.
+    pub(crate) fn poll_step(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<GnState<Y, O>, GnError>> {
+        self.poll_step_cancellable(cx).map(|result| match result? {
+            GnState::Suspended(value) => Ok(GnState::Suspended(value)),
+            GnState::Completed(Ok(value)) => Ok(GnState::Completed(value)),
+            // Only reachable when a cancellation request is found in the slot unexpectedly
+            GnState::Completed(Err(Cancelled)) => Err(GnError::SlotProtocol),
+        })
+    }
+
+    fn poll_step_cancellable(&mut self, cx: &mut Context<'_>) -> Poll<CancellableStep<Y, O>> {
+        let Some(generator) = self.generator.as_mut() else {
+            return Poll::Ready(Err(GnError::Completed));
+        };
+
+        // The flag is cleared only if polling the future does not panic
+        self.poisoned = true;
+        let output = generator.as_mut().poll(cx);
+        self.poisoned = false;
+
+        let result = match output {
+            Poll::Pending => match F::cell_try_replace(&self.slot.0, Slot::Empty) {
+                Ok(Slot::YieldValue(value)) => {
+                    let (lower, upper) = self.slot.size_hint();
+                    self.slot.set_size_hint((
+                        lower.saturating_sub(1),
+                        upper.map(|upper| upper.saturating_sub(1)),
+                    ));
+                    Ok(GnState::Suspended(value))
+                }
+                Ok(Slot::Unresumed) => Err(GnError::SlotProtocol),
+                // The generator is waiting on another future
+                Ok(Slot::Empty) => return Poll::Pending,
+                // Same, but the resume value was not retrieved yet: put it back for later
+                Ok(Slot::ResumeValue(value)) => match self.write_resume_value(value) {
+                    Ok(()) => return Poll::Pending,
+                    Err(error) => Err(error),
+                },
+                Ok(Slot::Cancel) => {
+                    // The cancellation request was not handled by the generator
+                    self.generator = None;
+                    Ok(GnState::Completed(Err(Cancelled)))
+                }
+                Err(_) => Err(GnError::AlreadyBorrowed),
+            },
+            Poll::Ready(value) => {
+                self.generator = None;
+                Ok(GnState::Completed(Ok(value)))
             }
-            Some(value) => GnState::Completed(value),
+        };
+
+        Poll::Ready(result)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<'gen, 'slot, Y, R, O, F: GeneratorFlavor> Gn<'gen, 'slot, Y, R, O, F> {
+    /// Starts execution of the generator, catching any panic
+    ///
+    /// See [`resume_catch_unwind`](Self::resume_catch_unwind).
+    pub fn start_catch_unwind(&mut self) -> Result<GnState<Y, O>, CatchUnwindError> {
+        catch_unwind(|| self.try_start())
+    }
+
+    /// Resumes execution of the generator, catching any panic
+    ///
+    /// When the generator panics, the panic payload is returned in
+    /// [`CatchUnwindError::Panicked`] and the generator is [poisoned](Self::is_poisoned). On
+    /// misuse, [`CatchUnwindError::Generator`] is returned instead.
+    pub fn resume_catch_unwind(&mut self, value: R) -> Result<GnState<Y, O>, CatchUnwindError> {
+        catch_unwind(|| self.try_resume(value))
+    }
+}
+
+/// Common interface of generators and generator adapters
+///
+/// This is implemented by [`Gn`] for every flavor, as well as by the adapters found in the
+/// [`combinator`] module.
+#[must_use = "generators do nothing unless you `.start()` or `.resume(…)` them"]
+pub trait Generator {
+    /// The type of the values yielded by the generator
+    type Yield;
+    /// The type of the values passed in when resuming the generator
+    type Resume;
+    /// The type of the value returned by the generator on completion
+    type Output;
+
+    /// Starts execution of the generator, returning an error on misuse
+    fn try_start(&mut self) -> Result<GnState<Self::Yield, Self::Output>, GnError>;
+
+    /// Starts execution of the generator, passing in a first value and returning an error on misuse
+    fn try_start_with(
+        &mut self,
+        value: Self::Resume,
+    ) -> Result<GnState<Self::Yield, Self::Output>, GnError>;
+
+    /// Resumes execution of the generator, passing in a value and returning an error on misuse
+    fn try_resume(
+        &mut self,
+        value: Self::Resume,
+    ) -> Result<GnState<Self::Yield, Self::Output>, GnError>;
+
+    /// Returns whether the generator was started or not
+    fn started(&self) -> bool;
+
+    /// Returns whether the generator completed or not
+    fn is_completed(&self) -> bool;
+
+    /// Starts execution of the generator
+    ///
+    /// # Panics
+    ///
+    /// Panics on misuse, see [`try_start`](Self::try_start) for a non-panicking alternative.
+    #[track_caller]
+    fn start(&mut self) -> GnState<Self::Yield, Self::Output> {
+        match self.try_start() {
+            Ok(state) => state,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Starts execution of the generator, passing in a first value
+    ///
+    /// # Panics
+    ///
+    /// Panics on misuse, see [`try_start_with`](Self::try_start_with) for a non-panicking
+    /// alternative.
+    #[track_caller]
+    fn start_with(&mut self, value: Self::Resume) -> GnState<Self::Yield, Self::Output> {
+        match self.try_start_with(value) {
+            Ok(state) => state,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Resumes execution of the generator, passing in a value
+    ///
+    /// # Panics
+    ///
+    /// Panics on misuse, see [`try_resume`](Self::try_resume) for a non-panicking alternative.
+    #[track_caller]
+    fn resume(&mut self, value: Self::Resume) -> GnState<Self::Yield, Self::Output> {
+        match self.try_resume(value) {
+            Ok(state) => state,
+            Err(error) => panic!("{error}"),
         }
     }
+
+    /// Transforms each yielded value using a closure
+    fn map_yield<Func, Y2>(self, f: Func) -> combinator::MapYield<Self, Func>
+    where
+        Self: Sized,
+        Func: FnMut(Self::Yield) -> Y2,
+    {
+        combinator::MapYield::new(self, f)
+    }
+
+    /// Transforms each resume value using a closure before passing it to the generator
+    fn map_resume<Func, R2>(self, f: Func) -> combinator::MapResume<Self, Func, R2>
+    where
+        Self: Sized,
+        Func: FnMut(R2) -> Self::Resume,
+    {
+        combinator::MapResume::new(self, f)
+    }
+
+    /// Transforms the output of the generator using a closure
+    fn map_output<Func, O2>(self, f: Func) -> combinator::MapOutput<Self, Func>
+    where
+        Self: Sized,
+        Func: FnMut(Self::Output) -> O2,
+    {
+        combinator::MapOutput::new(self, f)
+    }
+
+    /// Filters and transforms yielded values using a closure
+    ///
+    /// When the closure returns `None`, the value is skipped and the generator is resumed right
+    /// away with the default resume value.
+    fn filter_map_yield<Func, Y2>(self, f: Func) -> combinator::FilterMapYield<Self, Func>
+    where
+        Self: Sized,
+        Self::Resume: Default,
+        Func: FnMut(Self::Yield) -> Option<Y2>,
+    {
+        combinator::FilterMapYield::new(self, f)
+    }
+
+    /// Chains a second generator, built from the output of this one
+    ///
+    /// The second generator is started as soon as this one completes.
+    fn then<Func, G>(self, f: Func) -> combinator::Then<Self, Func, G>
+    where
+        Self: Sized,
+        Func: FnOnce(Self::Output) -> G,
+        G: Generator<Yield = Self::Yield, Resume = Self::Resume>,
+    {
+        combinator::Then::new(self, f)
+    }
+
+    /// Connects this generator to a downstream generator consuming its yielded values
+    ///
+    /// See [`Pipe`](combinator::Pipe) for the rules driving both generators.
+    fn pipe<G>(self, downstream: G) -> combinator::Pipe<Self, G>
+    where
+        Self: Sized,
+        G: Generator<Resume = Self::Yield>,
+    {
+        combinator::Pipe::new(self, downstream)
+    }
+
+    /// Drives the generator until completion using a handler, returning its output
+    ///
+    /// See the [`drive` module](crate::drive).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator was already started.
+    #[track_caller]
+    fn run_with<H>(self, handler: H) -> Self::Output
+    where
+        Self: Sized,
+        H: drive::Handler<Self::Yield, Self::Resume>,
+    {
+        drive::Driver::new(self, handler).run()
+    }
+
+    /// Returns a driver executing the generator step by step using a handler
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator was already started.
+    #[track_caller]
+    fn drive<H>(self, handler: H) -> drive::Driver<Self, H>
+    where
+        Self: Sized,
+        H: drive::Handler<Self::Yield, Self::Resume>,
+    {
+        drive::Driver::new(self, handler)
+    }
+}
+
+impl<G: Generator + ?Sized> Generator for &mut G {
+    type Yield = G::Yield;
+    type Resume = G::Resume;
+    type Output = G::Output;
+
+    fn try_start(&mut self) -> Result<GnState<Self::Yield, Self::Output>, GnError> {
+        (**self).try_start()
+    }
+
+    fn try_start_with(
+        &mut self,
+        value: Self::Resume,
+    ) -> Result<GnState<Self::Yield, Self::Output>, GnError> {
+        (**self).try_start_with(value)
+    }
+
+    fn try_resume(
+        &mut self,
+        value: Self::Resume,
+    ) -> Result<GnState<Self::Yield, Self::Output>, GnError> {
+        (**self).try_resume(value)
+    }
+
+    fn started(&self) -> bool {
+        (**self).started()
+    }
+
+    fn is_completed(&self) -> bool {
+        (**self).is_completed()
+    }
+}
+
+impl<'gen, 'slot, Y, R, O, F: GeneratorFlavor> Generator for Gn<'gen, 'slot, Y, R, O, F> {
+    type Yield = Y;
+    type Resume = R;
+    type Output = O;
+
+    fn try_start(&mut self) -> Result<GnState<Y, O>, GnError> {
+        Gn::try_start(self)
+    }
+
+    fn try_start_with(&mut self, value: R) -> Result<GnState<Y, O>, GnError> {
+        Gn::try_start_with(self, value)
+    }
+
+    fn try_resume(&mut self, value: R) -> Result<GnState<Y, O>, GnError> {
+        Gn::try_resume(self, value)
+    }
+
+    fn started(&self) -> bool {
+        Gn::started(self)
+    }
+
+    fn is_completed(&self) -> bool {
+        Gn::is_completed(self)
+    }
+}
+
+#[cfg(feature = "std")]
+fn catch_unwind<T>(f: impl FnOnce() -> Result<T, GnError>) -> Result<T, CatchUnwindError> {
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)) {
+        Ok(result) => Ok(result?),
+        Err(payload) => Err(CatchUnwindError::Panicked(payload)),
+    }
 }
 
 #[must_use]
@@ -304,16 +1175,19 @@ fn noop_waker() -> Waker {
     unsafe { Waker::from_raw(RAW) }
 }
 
-fn execute_one_step<F: Future + ?Sized>(generator: Pin<&mut F>) -> Option<F::Output> {
+/// Polls using a no-op waker, waiting on a future other than [`Interrupt`] being an error
+fn without_waker<T>(
+    poll: impl FnOnce(&mut Context<'_>) -> Poll<Result<T, GnError>>,
+) -> Result<T, GnError> {
     // TODO: use Waker::noop when stabilized
     // https://doc.rust-lang.org/std/task/struct.Waker.html#method.noop
     let noop_waker = noop_waker();
 
     let mut context = Context::from_waker(&noop_waker);
 
-    match generator.poll(&mut context) {
-        Poll::Pending => None,
-        Poll::Ready(item) => Some(item),
+    match poll(&mut context) {
+        Poll::Pending => Err(GnError::ForeignFuture),
+        Poll::Ready(result) => result,
     }
 }
 
@@ -324,6 +1198,10 @@ where
     type Item = Y;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.is_completed() {
+            return None;
+        }
+
         let state = if self.started {
             self.resume(())
         } else {
@@ -335,4 +1213,10 @@ where
             GnState::Completed(()) => None,
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.yield_hint()
+    }
 }
+
+impl<'gen, 'slot, Y, F> FusedIterator for Gn<'gen, 'slot, Y, (), (), F> where F: GeneratorFlavor {}
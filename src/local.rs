@@ -38,6 +38,10 @@ mod stacked {
         fn cell_replace<T>(cell: &Self::Cell<T>, other: T) -> T {
             cell.replace(other)
         }
+
+        fn cell_try_get<T: Copy + Default>(cell: &Self::Cell<T>) -> Option<T> {
+            Some(cell.get())
+        }
     }
 
     impl StackFlavor for StackLocal {}
@@ -53,11 +57,7 @@ mod stacked {
             slot: &'slot StackCellSlot<Y, R>,
             generator: Pin<&'gen mut (dyn Future<Output = O> + 'gen)>,
         ) -> Self {
-            Self {
-                slot,
-                generator,
-                started: false,
-            }
+            Self::from_parts(slot, generator)
         }
     }
 }
@@ -94,6 +94,10 @@ mod heap {
         fn cell_replace<T>(cell: &Self::Cell<T>, other: T) -> T {
             cell.replace(other)
         }
+
+        fn cell_try_get<T: Copy + Default>(cell: &Self::Cell<T>) -> Option<T> {
+            Some(cell.get())
+        }
     }
 
     impl HeapFlavor for HeapLocal {
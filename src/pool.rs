@@ -0,0 +1,329 @@
+//! Multi-threaded execution of many thread-safe generators
+//!
+//! A [`ThreadPool`] executes generators such as [`sync::StaticGn`](crate::sync::StaticGn) on
+//! worker threads, one step at a time. Each task is associated to its own [`Handler`], which
+//! computes the resume value for each yielded value. Between two steps, a task is put back in the
+//! queue of its worker, where idle workers may steal it: tasks migrate between workers so that
+//! all cores are kept busy.
+//!
+//! ```
+//! use genoise::pool::ThreadPool;
+//! use genoise::sync::{Co, Gn};
+//!
+//! async fn collatz(mut co: Co<'_, u64, ()>, mut n: u64) -> usize {
+//!     let mut steps = 0;
+//!     while n != 1 {
+//!         n = if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+//!         co.suspend(n).await;
+//!         steps += 1;
+//!     }
+//!     steps
+//! }
+//!
+//! let pool = ThreadPool::with_threads(4);
+//!
+//! let handles: Vec<_> = (1..=20)
+//!     .map(|n| pool.spawn(Gn::new(move |co| collatz(co, n)), |_| ()))
+//!     .collect();
+//!
+//! let steps: Vec<usize> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+//! assert_eq!(steps[6], 16);
+//! ```
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread;
+
+use crate::drive::{Driver, Handler};
+use crate::Generator;
+
+/// Handle to the output of a task spawned on a [`ThreadPool`]
+pub struct TaskHandle<O> {
+    completion: Arc<Completion<O>>,
+}
+
+impl<O> TaskHandle<O> {
+    /// Returns whether the task completed, either by returning or by panicking
+    pub fn is_finished(&self) -> bool {
+        lock(&self.completion.result).is_some()
+    }
+
+    /// Waits for the task to complete, returning its output
+    ///
+    /// If the generator or its handler panicked, the panic payload is returned as an error.
+    pub fn join(self) -> thread::Result<O> {
+        let mut result = lock(&self.completion.result);
+
+        loop {
+            match result.take() {
+                Some(result) => return result,
+                None => {
+                    result = self
+                        .completion
+                        .ready
+                        .wait(result)
+                        .unwrap_or_else(PoisonError::into_inner)
+                }
+            }
+        }
+    }
+}
+
+struct Completion<O> {
+    result: Mutex<Option<thread::Result<O>>>,
+    ready: Condvar,
+}
+
+trait Task: Send {
+    /// Executes one step, returning whether the task completed
+    fn step(&mut self) -> bool;
+}
+
+struct Spawned<G: Generator, H> {
+    driver: Driver<G, H>,
+    completion: Arc<Completion<G::Output>>,
+}
+
+impl<G, H> Task for Spawned<G, H>
+where
+    G: Generator + Send,
+    G::Yield: Send,
+    G::Output: Send,
+    H: Handler<G::Yield, G::Resume> + Send,
+{
+    fn step(&mut self) -> bool {
+        let result = match catch_unwind(AssertUnwindSafe(|| self.driver.step())) {
+            Ok(None) => return false,
+            Ok(Some(output)) => Ok(output),
+            Err(payload) => Err(payload),
+        };
+
+        *lock(&self.completion.result) = Some(result);
+        self.completion.ready.notify_all();
+
+        true
+    }
+}
+
+struct Shared {
+    /// Queue for the tasks spawned from outside the pool
+    injector: Mutex<VecDeque<Box<dyn Task>>>,
+    /// Number of tasks in the injector, checked before locking it
+    injected: AtomicUsize,
+    /// Queue of each worker
+    locals: Vec<Mutex<VecDeque<Box<dyn Task>>>>,
+    /// Number of tasks which did not complete yet
+    alive: AtomicUsize,
+    /// Number of workers waiting for a task
+    sleeping: AtomicUsize,
+    shutdown: AtomicBool,
+    /// Held by workers going to sleep, so that wake ups are not lost
+    sleep: Mutex<()>,
+    wake: Condvar,
+}
+
+impl Shared {
+    fn inject(&self, task: Box<dyn Task>) {
+        self.alive.fetch_add(1, Ordering::SeqCst);
+
+        let mut injector = lock(&self.injector);
+        injector.push_back(task);
+        self.injected.fetch_add(1, Ordering::SeqCst);
+        drop(injector);
+
+        let _sleep = lock(&self.sleep);
+        self.wake.notify_one();
+    }
+
+    /// Puts a task back in the queue of its worker after a step
+    ///
+    /// A sleeping worker is only woken up if the queue holds more tasks than the worker is about
+    /// to take back, so that it may steal one of them.
+    fn requeue(&self, worker: usize, task: Box<dyn Task>) {
+        let mut queue = lock(&self.locals[worker]);
+        queue.push_back(task);
+        let len = queue.len();
+        drop(queue);
+
+        if len > 1 && self.sleeping.load(Ordering::SeqCst) > 0 {
+            let _sleep = lock(&self.sleep);
+            self.wake.notify_one();
+        }
+    }
+
+    /// Takes a task, preferring new tasks, then the queue of the worker, then stealing
+    fn find(&self, worker: usize) -> Option<Box<dyn Task>> {
+        if self.injected.load(Ordering::SeqCst) > 0 {
+            let mut injector = lock(&self.injector);
+
+            if let Some(task) = injector.pop_front() {
+                self.injected.fetch_sub(1, Ordering::SeqCst);
+                return Some(task);
+            }
+        }
+
+        if let Some(task) = lock(&self.locals[worker]).pop_front() {
+            return Some(task);
+        }
+
+        let count = self.locals.len();
+
+        (1..count)
+            .map(|offset| (worker + offset) % count)
+            .find_map(|victim| lock(&self.locals[victim]).pop_back())
+    }
+
+    /// Blocks until a task is available, returning `None` if the pool is shut down instead
+    fn sleep(&self, worker: usize) -> Option<Box<dyn Task>> {
+        let mut sleep = lock(&self.sleep);
+        self.sleeping.fetch_add(1, Ordering::SeqCst);
+
+        let task = loop {
+            if let Some(task) = self.find(worker) {
+                break Some(task);
+            }
+
+            if self.shutdown.load(Ordering::SeqCst) && self.alive.load(Ordering::SeqCst) == 0 {
+                break None;
+            }
+
+            sleep = self
+                .wake
+                .wait(sleep)
+                .unwrap_or_else(PoisonError::into_inner);
+        };
+
+        self.sleeping.fetch_sub(1, Ordering::SeqCst);
+        task
+    }
+
+    fn complete(&self) {
+        if self.alive.fetch_sub(1, Ordering::SeqCst) == 1 {
+            let _sleep = lock(&self.sleep);
+            self.wake.notify_all();
+        }
+    }
+}
+
+/// Executes many thread-safe generators on worker threads
+///
+/// Dropping the pool waits for all the tasks to complete.
+pub struct ThreadPool {
+    shared: Arc<Shared>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    /// Creates a pool with one worker per available core
+    pub fn new() -> Self {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        Self::with_threads(threads)
+    }
+
+    /// Creates a pool with the given number of worker threads
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is zero.
+    #[track_caller]
+    pub fn with_threads(threads: usize) -> Self {
+        assert!(threads > 0, "a thread pool requires at least one worker");
+
+        let shared = Arc::new(Shared {
+            injector: Mutex::new(VecDeque::new()),
+            injected: AtomicUsize::new(0),
+            locals: (0..threads).map(|_| Mutex::new(VecDeque::new())).collect(),
+            alive: AtomicUsize::new(0),
+            sleeping: AtomicUsize::new(0),
+            shutdown: AtomicBool::new(false),
+            sleep: Mutex::new(()),
+            wake: Condvar::new(),
+        });
+
+        let workers = (0..threads)
+            .map(|worker| {
+                let shared = Arc::clone(&shared);
+                thread::Builder::new()
+                    .name(alloc::format!("genoise-worker-{worker}"))
+                    .spawn(move || run_worker(&shared, worker))
+                    .expect("failed to spawn a worker thread")
+            })
+            .collect();
+
+        Self { shared, workers }
+    }
+
+    /// Returns the number of worker threads
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Spawns a task executing the generator using the handler
+    ///
+    /// The generator must not be started yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator was already started.
+    #[track_caller]
+    pub fn spawn<G, H>(&self, generator: G, handler: H) -> TaskHandle<G::Output>
+    where
+        G: Generator + Send + 'static,
+        G::Yield: Send,
+        G::Output: Send,
+        H: Handler<G::Yield, G::Resume> + Send + 'static,
+    {
+        let completion = Arc::new(Completion {
+            result: Mutex::new(None),
+            ready: Condvar::new(),
+        });
+
+        let task = Spawned {
+            driver: Driver::new(generator, handler),
+            completion: Arc::clone(&completion),
+        };
+
+        self.shared.inject(Box::new(task));
+
+        TaskHandle { completion }
+    }
+}
+
+impl Default for ThreadPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        let sleep = lock(&self.shared.sleep);
+        self.shared.shutdown.store(true, Ordering::SeqCst);
+        self.shared.wake.notify_all();
+        drop(sleep);
+
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn run_worker(shared: &Shared, worker: usize) {
+    while let Some(mut task) = shared.find(worker).or_else(|| shared.sleep(worker)) {
+        if task.step() {
+            shared.complete();
+        } else {
+            shared.requeue(worker, task);
+        }
+    }
+}
+
+/// Locks a mutex, ignoring poisoning: panics of tasks are caught before they reach a lock
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
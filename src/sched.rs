@@ -0,0 +1,221 @@
+//! Single-threaded cooperative scheduling of many generators
+//!
+//! Each task is a generator associated to its own [`Handler`], which computes the resume value
+//! for each yielded value. The [`Scheduler`] executes one step of a task at a time: tasks with
+//! a higher priority are always executed first, and tasks sharing the same priority are executed
+//! in a round-robin fashion.
+//!
+//! ```
+//! use genoise::local::{Co, Gn};
+//! use genoise::sched::Scheduler;
+//!
+//! async fn session(mut co: Co<'_, u32, u32>, id: u32) -> u32 {
+//!     let mut total = 0;
+//!     for _ in 0..3 {
+//!         total += co.suspend(id).await;
+//!     }
+//!     total
+//! }
+//!
+//! let mut scheduler = Scheduler::new();
+//! let first = scheduler.spawn(Gn::new(|co| session(co, 1)), |id| id * 10);
+//! let second = scheduler.spawn(Gn::new(|co| session(co, 2)), |id| id * 100);
+//! scheduler.run();
+//!
+//! assert_eq!(first.take_output(), Some(30));
+//! assert_eq!(second.take_output(), Some(600));
+//! ```
+
+use alloc::boxed::Box;
+use alloc::collections::BinaryHeap;
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use core::cmp::{Ordering, Reverse};
+
+use crate::drive::{Driver, Handler};
+use crate::Generator;
+
+/// Handle to the output of a task spawned on a [`Scheduler`]
+pub struct JoinHandle<O> {
+    output: Rc<RefCell<Option<O>>>,
+    id: TaskId,
+}
+
+impl<O> JoinHandle<O> {
+    /// Returns the identifier of the task
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Returns whether the task completed and its output was not taken yet
+    pub fn is_finished(&self) -> bool {
+        self.output.borrow().is_some()
+    }
+
+    /// Takes the output of the task, if it completed
+    pub fn take_output(&self) -> Option<O> {
+        self.output.borrow_mut().take()
+    }
+}
+
+/// Identifier of a task spawned on a [`Scheduler`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TaskId(u64);
+
+trait Task {
+    /// Executes one step, returning whether the task completed
+    fn step(&mut self) -> bool;
+}
+
+struct Spawned<G: Generator, H> {
+    driver: Driver<G, H>,
+    output: Rc<RefCell<Option<G::Output>>>,
+}
+
+impl<G, H> Task for Spawned<G, H>
+where
+    G: Generator,
+    H: Handler<G::Yield, G::Resume>,
+{
+    fn step(&mut self) -> bool {
+        match self.driver.step() {
+            Some(output) => {
+                *self.output.borrow_mut() = Some(output);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+struct Entry<'a> {
+    priority: i32,
+    /// Increased each time a task is queued, so that tasks sharing a priority are executed in turn
+    sequence: u64,
+    id: TaskId,
+    task: Box<dyn Task + 'a>,
+}
+
+impl Entry<'_> {
+    fn key(&self) -> (i32, Reverse<u64>) {
+        (self.priority, Reverse(self.sequence))
+    }
+}
+
+impl PartialEq for Entry<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Entry<'_> {}
+
+impl PartialOrd for Entry<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// Executes many generators cooperatively on the current thread
+#[derive(Default)]
+pub struct Scheduler<'a> {
+    queue: BinaryHeap<Entry<'a>>,
+    sequence: u64,
+    next_id: u64,
+}
+
+impl<'a> Scheduler<'a> {
+    /// Creates a scheduler without any task
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of tasks which did not complete yet
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns whether all tasks completed
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Spawns a task with the default priority (`0`)
+    ///
+    /// The generator must not be started yet.
+    pub fn spawn<G, H>(&mut self, generator: G, handler: H) -> JoinHandle<G::Output>
+    where
+        G: Generator + 'a,
+        H: Handler<G::Yield, G::Resume> + 'a,
+    {
+        self.spawn_with_priority(0, generator, handler)
+    }
+
+    /// Spawns a task with the given priority
+    ///
+    /// Tasks with a higher priority are executed first. The generator must not be started yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator was already started.
+    #[track_caller]
+    pub fn spawn_with_priority<G, H>(
+        &mut self,
+        priority: i32,
+        generator: G,
+        handler: H,
+    ) -> JoinHandle<G::Output>
+    where
+        G: Generator + 'a,
+        H: Handler<G::Yield, G::Resume> + 'a,
+    {
+        let output = Rc::new(RefCell::new(None));
+        let id = TaskId(self.next_id);
+        self.next_id += 1;
+
+        let task = Spawned {
+            driver: Driver::new(generator, handler),
+            output: Rc::clone(&output),
+        };
+
+        self.push(Entry {
+            priority,
+            sequence: 0,
+            id,
+            task: Box::new(task),
+        });
+
+        JoinHandle { output, id }
+    }
+
+    /// Executes one step of the next task, returning its identifier
+    ///
+    /// `None` is returned if all tasks completed.
+    pub fn run_one(&mut self) -> Option<TaskId> {
+        let mut entry = self.queue.pop()?;
+        let id = entry.id;
+
+        if !entry.task.step() {
+            self.push(entry);
+        }
+
+        Some(id)
+    }
+
+    /// Executes tasks until all of them complete
+    pub fn run(&mut self) {
+        while self.run_one().is_some() {}
+    }
+
+    fn push(&mut self, mut entry: Entry<'a>) {
+        entry.sequence = self.sequence;
+        self.sequence += 1;
+        self.queue.push(entry);
+    }
+}
@@ -0,0 +1,179 @@
+//! Generators which can be sent to another thread, but not shared between threads
+//!
+//! The [`sync`](crate::sync) flavors require the future to be `Send + Sync`, which rules out
+//! bodies holding a [`Cell`](core::cell::Cell), a [`RefCell`](core::cell::RefCell) or a
+//! receiver across a suspension point. The flavors of this module only require the future to be
+//! `Send`: the generator is `Send`, but not `Sync`. Since the generator is always driven through
+//! `&mut`, it is never accessed from two threads at the same time.
+//!
+//! ```
+//! use std::cell::Cell;
+//!
+//! use genoise::send::{Co, Gn};
+//! use genoise::GnState;
+//!
+//! async fn counter(mut co: Co<'_, u32, ()>) {
+//!     let count = Cell::new(0);
+//!     while count.get() < 3 {
+//!         co.suspend(count.get()).await;
+//!         count.set(count.get() + 1);
+//!     }
+//! }
+//!
+//! let mut generator = Gn::new(counter);
+//!
+//! let handle = std::thread::spawn(move || {
+//!     assert!(matches!(generator.start(), GnState::Suspended(0)));
+//!     generator
+//! });
+//!
+//! let mut generator = handle.join().unwrap();
+//! assert!(matches!(generator.resume(()), GnState::Suspended(1)));
+//! ```
+
+pub use stack::*;
+
+mod stack {
+    use core::{future::Future, pin::Pin};
+
+    use crate::sync::cell::SyncRefCell;
+    use crate::{GeneratorFlavor, StackFlavor};
+
+    pub struct StackSend;
+
+    /// Helper to construct a stacked generator which can be sent to another thread
+    #[doc(hidden)]
+    #[macro_export]
+    macro_rules! let_send_gen {
+        ($gn:ident, |$co:ident| $fut_init:block) => {
+            $crate::let_gen!($crate::send::StackSend, $gn, |$co| $fut_init)
+        };
+        ($gn:ident, $fut_init:path) => {
+            $crate::let_gen!($crate::send::StackSend, $gn, |co| { $fut_init(co) })
+        };
+    }
+
+    #[doc(inline)]
+    pub use let_send_gen as let_gen;
+
+    impl GeneratorFlavor for StackSend {
+        type Fut<'a, T: 'a> = dyn Future<Output = T> + Send + 'a;
+
+        type UniquePtr<'a, T: ?Sized + 'a> = &'a mut T;
+
+        type SharedPtr<'a, T: ?Sized + 'a> = &'a T;
+
+        type Cell<T> = SyncRefCell<T>;
+
+        fn new_cell<T>(value: T) -> Self::Cell<T> {
+            SyncRefCell::new(value)
+        }
+
+        fn cell_replace<T>(cell: &Self::Cell<T>, other: T) -> T {
+            cell.replace(other)
+        }
+
+        fn cell_try_replace<T>(cell: &Self::Cell<T>, other: T) -> Result<T, T> {
+            cell.try_replace(other)
+        }
+
+        fn cell_try_get<T: Copy + Default>(cell: &Self::Cell<T>) -> Option<T> {
+            cell.try_get()
+        }
+    }
+
+    impl StackFlavor for StackSend {}
+
+    pub type StackCellSlot<Y, R> = crate::CellSlot<Y, R, StackSend>;
+
+    pub type StackCo<'slot, Y, R> = crate::Co<'slot, Y, R, StackSend>;
+
+    pub type StackGn<'gen, 'slot, Y, R, O> = crate::Gn<'gen, 'slot, Y, R, O, StackSend>;
+
+    impl<'gen, 'slot, Y, R, O> StackGn<'gen, 'slot, Y, R, O> {
+        pub fn new(
+            slot: &'slot StackCellSlot<Y, R>,
+            generator: Pin<&'gen mut (dyn Future<Output = O> + Send + 'gen)>,
+        ) -> Self {
+            Self::from_parts(slot, generator)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub use self::heap::*;
+
+#[cfg(feature = "alloc")]
+mod heap {
+    use alloc::boxed::Box;
+    use alloc::sync::Arc;
+    use core::future::Future;
+
+    use crate::sync::cell::SyncRefCell;
+    use crate::{CellSlot, GeneratorFlavor, HeapFlavor};
+
+    /// Flavor for `Send` generators which are not `Sync`
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub struct HeapSend;
+
+    impl GeneratorFlavor for HeapSend {
+        type Fut<'a, T: 'a> = dyn Future<Output = T> + Send + 'a;
+
+        type UniquePtr<'a, T: ?Sized + 'a> = Box<T>;
+
+        type SharedPtr<'a, T: ?Sized + 'a> = Arc<T>;
+
+        type Cell<T> = SyncRefCell<T>;
+
+        fn new_cell<T>(value: T) -> Self::Cell<T> {
+            SyncRefCell::new(value)
+        }
+
+        fn cell_replace<T>(cell: &Self::Cell<T>, other: T) -> T {
+            cell.replace(other)
+        }
+
+        fn cell_try_replace<T>(cell: &Self::Cell<T>, other: T) -> Result<T, T> {
+            cell.try_replace(other)
+        }
+
+        fn cell_try_get<T: Copy + Default>(cell: &Self::Cell<T>) -> Option<T> {
+            cell.try_get()
+        }
+    }
+
+    impl HeapFlavor for HeapSend {
+        fn new_shared<'a, T: 'a>(value: T) -> Self::SharedPtr<'a, T> {
+            Arc::new(value)
+        }
+    }
+
+    /// Generator controller for `Send` generators
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub type Co<'slot, Y, R> = crate::Co<'slot, Y, R, HeapSend>;
+
+    /// Generator controller for `Send` generators holding items with 'static lifetime only
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub type StaticCo<Y, R> = Co<'static, Y, R>;
+
+    /// Generator which can be sent to another thread
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub type Gn<'gen, 'slot, Y, R, O> = crate::Gn<'gen, 'slot, Y, R, O, HeapSend>;
+
+    impl<'gen, 'slot, Y, R, O> Gn<'gen, 'slot, Y, R, O> {
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        pub fn new<Producer, Generator>(producer: Producer) -> Self
+        where
+            Producer: FnOnce(Co<'slot, Y, R>) -> Generator,
+            Generator: Future<Output = O> + Send + 'gen,
+        {
+            let co = Co::new_heap(CellSlot::default());
+            let slots = Arc::clone(&co.slot);
+            let generator = Box::pin(producer(co));
+            Self::from_parts(slots, generator)
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub type StaticGn<Y, R, O> = crate::Gn<'static, 'static, Y, R, O, HeapSend>;
+}
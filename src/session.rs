@@ -0,0 +1,364 @@
+//! Session-typed protocols checked at compile time
+//!
+//! A protocol is described by a [`Session`] type built from [`Tx`], [`Rx`] and [`End`]. The
+//! generator body drives a [`Chan`] whose type changes after each message, and the caller drives
+//! an [`Endpoint`] following the [dual](Session::Dual) protocol. Sending or receiving a message out
+//! of order is a compile error on both sides.
+//!
+//! ```
+//! use genoise::local::Gn;
+//! use genoise::session::{Chan, End, Endpoint, Rx, Tx};
+//!
+//! /// Protocol followed by the generator
+//! type Login = Tx<String, Rx<bool, End>>;
+//!
+//! let generator = Gn::new(|co| async {
+//!     let chan = Chan::<Login, _>::new(co);
+//!     let chan = chan.send("admin".to_owned()).await;
+//!     let (accepted, chan) = chan.recv().await;
+//!     chan.close(accepted)
+//! });
+//!
+//! let endpoint = Endpoint::new(generator);
+//! let (user, endpoint) = endpoint.recv();
+//! let endpoint = endpoint.send(user == "admin");
+//! assert!(endpoint.close());
+//! ```
+
+use alloc::boxed::Box;
+use core::any::Any;
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::{CellSlot, Co, Generator, GeneratorFlavor, GnState};
+
+/// Sends a value of type `T`, then follows the protocol `S`
+pub struct Tx<T, S>(PhantomData<fn() -> (T, S)>);
+
+/// Receives a value of type `T`, then follows the protocol `S`
+pub struct Rx<T, S>(PhantomData<fn() -> (T, S)>);
+
+/// Terminates the protocol
+pub struct End;
+
+/// A protocol, as seen from one side
+pub trait Session: private::Sealed {
+    /// The same protocol, as seen from the other side
+    type Dual: Session;
+}
+
+impl<T, S: Session> Session for Tx<T, S> {
+    type Dual = Rx<T, S::Dual>;
+}
+
+impl<T, S: Session> Session for Rx<T, S> {
+    type Dual = Tx<T, S::Dual>;
+}
+
+impl Session for End {
+    type Dual = End;
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl<T, S> Sealed for super::Tx<T, S> {}
+    impl<T, S> Sealed for super::Rx<T, S> {}
+    impl Sealed for super::End {}
+}
+
+/// A [`GeneratorFlavor`] which can exchange [`Message`]s
+///
+/// The flavor determines the bounds required on the values sent: any `'static` value may be sent
+/// through local generators, while thread-safe generators require `Send` (and `Sync`) values.
+pub trait SessionFlavor: GeneratorFlavor {
+    /// The type-erased payload of the messages
+    type Payload: ?Sized + Payload;
+}
+
+impl SessionFlavor for crate::local::StackLocal {
+    type Payload = dyn Any;
+}
+
+impl SessionFlavor for crate::local::HeapLocal {
+    type Payload = dyn Any;
+}
+
+impl SessionFlavor for crate::send::StackSend {
+    type Payload = dyn Any + Send;
+}
+
+impl SessionFlavor for crate::send::HeapSend {
+    type Payload = dyn Any + Send;
+}
+
+impl SessionFlavor for crate::sync::StackSync {
+    type Payload = dyn Any + Send + Sync;
+}
+
+impl SessionFlavor for crate::sync::HeapSync {
+    type Payload = dyn Any + Send + Sync;
+}
+
+/// A type-erased value, as held by a [`Message`]
+pub trait Payload: private::Sealed + 'static {
+    /// Takes the value out, if it is of type `T`
+    fn downcast<T: Any>(self: Box<Self>) -> Option<T>;
+}
+
+/// A [`Payload`] which may hold values of type `T`
+pub trait Erase<T>: Payload {
+    /// Erases the type of a value
+    fn erase(value: T) -> Box<Self>;
+}
+
+macro_rules! impl_payload {
+    ($($payload:ty => $($bounds:path),*;)*) => {$(
+        impl Payload for $payload {
+            fn downcast<T: Any>(self: Box<Self>) -> Option<T> {
+                <Box<$payload>>::downcast::<T>(self).ok().map(|value| *value)
+            }
+        }
+
+        impl<T: Any $(+ $bounds)*> Erase<T> for $payload {
+            fn erase(value: T) -> Box<Self> {
+                Box::new(value)
+            }
+        }
+
+        impl private::Sealed for $payload {}
+    )*};
+}
+
+impl_payload! {
+    dyn Any => ;
+    dyn Any + Send => Send;
+    dyn Any + Send + Sync => Send, Sync;
+}
+
+/// A type-erased message exchanged between a [`Chan`] and an [`Endpoint`]
+pub struct Message<F: SessionFlavor>(Box<F::Payload>);
+
+impl<F: SessionFlavor> Message<F> {
+    fn new<T>(value: T) -> Self
+    where
+        F::Payload: Erase<T>,
+    {
+        Self(F::Payload::erase(value))
+    }
+
+    fn downcast<T: Any>(self) -> T {
+        match self.0.downcast::<T>() {
+            Some(value) => value,
+            None => panic!("message does not follow the session protocol"),
+        }
+    }
+}
+
+impl<F: SessionFlavor> fmt::Debug for Message<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Message").finish_non_exhaustive()
+    }
+}
+
+/// Generator controller following the protocol `P`, currently in state `S`
+pub struct Chan<'slot, P, F, S = P>
+where
+    F: SessionFlavor,
+    CellSlot<Option<Message<F>>, Option<Message<F>>, F>: 'slot,
+{
+    co: Co<'slot, Option<Message<F>>, Option<Message<F>>, F>,
+    _session: PhantomData<fn() -> (P, S)>,
+}
+
+impl<'slot, P, S, F> Chan<'slot, P, F, S>
+where
+    F: SessionFlavor,
+{
+    fn into_state<S2>(self) -> Chan<'slot, P, F, S2> {
+        Chan {
+            co: self.co,
+            _session: PhantomData,
+        }
+    }
+}
+
+impl<'slot, P, F> Chan<'slot, P, F>
+where
+    P: Session,
+    F: SessionFlavor,
+{
+    /// Wraps a generator controller, starting the protocol `P`
+    ///
+    /// The controller must not have been used to suspend the generator before: the protocol
+    /// starts with the first message, and values yielded through [`Co::suspend`] beforehand are
+    /// not part of it. An [`Endpoint`] driving such a generator panics at the first message which
+    /// does not follow the protocol, instead of failing to compile.
+    pub fn new(co: Co<'slot, Option<Message<F>>, Option<Message<F>>, F>) -> Self {
+        Self {
+            co,
+            _session: PhantomData,
+        }
+    }
+}
+
+impl<'slot, P, T, S, F> Chan<'slot, P, F, Tx<T, S>>
+where
+    F: SessionFlavor,
+    F::Payload: Erase<T>,
+{
+    /// Suspends the execution of the generator, sending a value
+    pub async fn send(mut self, value: T) -> Chan<'slot, P, F, S> {
+        self.co.suspend(Some(Message::new(value))).await;
+        self.into_state()
+    }
+}
+
+impl<'slot, P, T, S, F> Chan<'slot, P, F, Rx<T, S>>
+where
+    T: Any,
+    F: SessionFlavor,
+{
+    /// Suspends the execution of the generator, receiving a value
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator is resumed without a message, which only happens when it is
+    /// driven without an [`Endpoint`].
+    pub async fn recv(mut self) -> (T, Chan<'slot, P, F, S>) {
+        let message = self.co.suspend(None).await;
+        let message = message.expect("generator resumed without a message");
+        (message.downcast(), self.into_state())
+    }
+}
+
+impl<'slot, P, F> Chan<'slot, P, F, End>
+where
+    F: SessionFlavor,
+{
+    /// Terminates the protocol, wrapping the output of the generator
+    pub fn close<O>(self, output: O) -> Closed<P, O> {
+        Closed {
+            output,
+            _session: PhantomData,
+        }
+    }
+}
+
+/// The output of a generator which completed the protocol `P`
+///
+/// Returned by [`Chan::close`].
+pub struct Closed<P, O> {
+    output: O,
+    _session: PhantomData<fn() -> P>,
+}
+
+impl<P, O> Closed<P, O> {
+    /// Returns the output of the generator
+    pub fn into_output(self) -> O {
+        self.output
+    }
+}
+
+/// Drives a generator following the dual of the protocol `S`
+pub struct Endpoint<G: Generator, S> {
+    generator: G,
+    resume_value: G::Resume,
+    _session: PhantomData<fn() -> S>,
+}
+
+impl<G, P, O, F> Endpoint<G, P::Dual>
+where
+    G: Generator<Yield = Option<Message<F>>, Resume = Option<Message<F>>, Output = Closed<P, O>>,
+    P: Session,
+    F: SessionFlavor,
+{
+    /// Wraps a generator following the protocol `P`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator was already started.
+    #[track_caller]
+    pub fn new(generator: G) -> Self {
+        assert!(!generator.started(), "{}", crate::GnError::AlreadyStarted);
+
+        Self {
+            generator,
+            resume_value: None,
+            _session: PhantomData,
+        }
+    }
+}
+
+impl<G, S, F> Endpoint<G, S>
+where
+    G: Generator<Yield = Option<Message<F>>, Resume = Option<Message<F>>>,
+    F: SessionFlavor,
+{
+    fn advance(&mut self) -> GnState<Option<Message<F>>, G::Output> {
+        if self.generator.started() {
+            self.generator.resume(self.resume_value.take())
+        } else {
+            self.generator.start()
+        }
+    }
+
+    fn into_state<S2>(self, resume_value: Option<Message<F>>) -> Endpoint<G, S2> {
+        Endpoint {
+            generator: self.generator,
+            resume_value,
+            _session: PhantomData,
+        }
+    }
+}
+
+impl<G, T, S, F> Endpoint<G, Tx<T, S>>
+where
+    G: Generator<Yield = Option<Message<F>>, Resume = Option<Message<F>>>,
+    F: SessionFlavor,
+    F::Payload: Erase<T>,
+{
+    /// Sends a value to the generator
+    ///
+    /// The generator is executed until it is ready to receive the value.
+    #[track_caller]
+    pub fn send(mut self, value: T) -> Endpoint<G, S> {
+        match self.advance() {
+            GnState::Suspended(None) => self.into_state(Some(Message::new(value))),
+            _ => panic!("generator does not follow the session protocol"),
+        }
+    }
+}
+
+impl<G, T, S, F> Endpoint<G, Rx<T, S>>
+where
+    G: Generator<Yield = Option<Message<F>>, Resume = Option<Message<F>>>,
+    T: Any,
+    F: SessionFlavor,
+{
+    /// Receives a value from the generator
+    ///
+    /// The generator is executed until it sends the value.
+    #[track_caller]
+    pub fn recv(mut self) -> (T, Endpoint<G, S>) {
+        match self.advance() {
+            GnState::Suspended(Some(message)) => (message.downcast(), self.into_state(None)),
+            _ => panic!("generator does not follow the session protocol"),
+        }
+    }
+}
+
+impl<G, P, O, F> Endpoint<G, End>
+where
+    G: Generator<Yield = Option<Message<F>>, Resume = Option<Message<F>>, Output = Closed<P, O>>,
+    F: SessionFlavor,
+{
+    /// Terminates the protocol, executing the generator until completion and returning its output
+    #[track_caller]
+    pub fn close(mut self) -> O {
+        match self.advance() {
+            GnState::Completed(closed) => closed.output,
+            GnState::Suspended(_) => panic!("generator does not follow the session protocol"),
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! Driving generators from async code
+//!
+//! A [`Gn`] is driven with a no-op waker, so its body can't wait on anything else than
+//! [`Co::suspend`](crate::Co::suspend). Once converted into an [`AsyncGn`] using
+//! [`Gn::into_async`], the body may also await arbitrary futures: the waker of the caller is
+//! passed through, and the execution step is simply pending until these futures are ready.
+//!
+//! Iterator-like generators implement [`Stream`], a minimal equivalent of the trait found in the
+//! `futures` crate.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::{CellSlot, GeneratorFlavor, Gn, GnError, GnState};
+
+/// A sequence of values produced asynchronously
+pub trait Stream {
+    /// The type of the values produced by the stream
+    type Item;
+
+    /// Attempts to pull out the next value of the stream
+    ///
+    /// `Poll::Ready(None)` is returned once the stream is exhausted.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+
+    /// Returns the bounds on the remaining length of the stream
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+
+    /// Returns a future resolving to the next value of the stream
+    fn next(&mut self) -> Next<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Next { stream: self }
+    }
+}
+
+/// Future resolving to the next value of a [`Stream`]
+///
+/// Returned by [`Stream::next`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Next<'a, S: ?Sized> {
+    stream: &'a mut S,
+}
+
+impl<'a, S> Future for Next<'a, S>
+where
+    S: Stream + Unpin + ?Sized,
+{
+    type Output = Option<S::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.stream).poll_next(cx)
+    }
+}
+
+/// A generator whose body may await arbitrary futures
+///
+/// Returned by [`Gn::into_async`].
+#[must_use = "generators do nothing unless you `.try_start().await` or `.try_resume(…).await` them"]
+pub struct AsyncGn<'gen, 'slot, Y, R, O, F>
+where
+    O: 'gen,
+    CellSlot<Y, R, F>: 'slot,
+    F: GeneratorFlavor,
+{
+    inner: Gn<'gen, 'slot, Y, R, O, F>,
+    /// Set while an execution step is pending
+    in_progress: bool,
+}
+
+// The inner future is pinned on its own, so no field is structurally pinned
+impl<'gen, 'slot, Y, R, O, F> Unpin for AsyncGn<'gen, 'slot, Y, R, O, F> where F: GeneratorFlavor {}
+
+impl<'gen, 'slot, Y, R, O, F> Gn<'gen, 'slot, Y, R, O, F>
+where
+    F: GeneratorFlavor,
+{
+    /// Converts the generator into a generator driven from async code
+    pub fn into_async(self) -> AsyncGn<'gen, 'slot, Y, R, O, F> {
+        AsyncGn {
+            inner: self,
+            in_progress: false,
+        }
+    }
+}
+
+enum Begin<R> {
+    Start,
+    StartWith(R),
+    Resume(R),
+}
+
+impl<'gen, 'slot, Y, R, O, F> AsyncGn<'gen, 'slot, Y, R, O, F>
+where
+    F: GeneratorFlavor,
+{
+    /// Returns whether the generator was started or not
+    pub fn started(&self) -> bool {
+        self.inner.started()
+    }
+
+    /// Returns whether the generator completed or not
+    pub fn is_completed(&self) -> bool {
+        self.inner.is_completed()
+    }
+
+    /// Returns whether an execution step is pending
+    ///
+    /// This happens when a future returned by [`try_start`](Self::try_start) or
+    /// [`try_resume`](Self::try_resume) is dropped before completion. The pending step is then
+    /// continued using [`poll_step`](Self::poll_step).
+    pub fn is_in_progress(&self) -> bool {
+        self.in_progress
+    }
+
+    /// Starts execution of the generator
+    pub fn try_start(&mut self) -> Step<'_, 'gen, 'slot, Y, R, O, F> {
+        Step::new(self, Begin::Start)
+    }
+
+    /// Starts execution of the generator, passing in a first value
+    ///
+    /// See [`Gn::start_with`].
+    pub fn try_start_with(&mut self, value: R) -> Step<'_, 'gen, 'slot, Y, R, O, F> {
+        Step::new(self, Begin::StartWith(value))
+    }
+
+    /// Resumes execution of the generator, passing in a value
+    pub fn try_resume(&mut self, value: R) -> Step<'_, 'gen, 'slot, Y, R, O, F> {
+        Step::new(self, Begin::Resume(value))
+    }
+
+    /// Continues the pending execution step
+    ///
+    /// See [`is_in_progress`](Self::is_in_progress). [`GnError::NoStepInProgress`] is returned if
+    /// no execution step is pending.
+    pub fn poll_step(&mut self, cx: &mut Context<'_>) -> Poll<Result<GnState<Y, O>, GnError>> {
+        if !self.in_progress {
+            return Poll::Ready(Err(GnError::NoStepInProgress));
+        }
+
+        let poll = self.inner.poll_step(cx);
+
+        if poll.is_ready() {
+            self.in_progress = false;
+        }
+
+        poll
+    }
+
+    /// Returns the underlying generator
+    ///
+    /// # Panics
+    ///
+    /// Panics if an execution step is pending.
+    #[track_caller]
+    pub fn into_inner(self) -> Gn<'gen, 'slot, Y, R, O, F> {
+        assert!(!self.in_progress, "{}", GnError::StepInProgress);
+        self.inner
+    }
+
+    fn begin(&mut self, begin: Begin<R>) -> Result<(), GnError> {
+        if self.in_progress {
+            return Err(GnError::StepInProgress);
+        }
+
+        match begin {
+            Begin::Start => self.inner.begin_start()?,
+            Begin::StartWith(value) => self.inner.begin_start_with(value)?,
+            Begin::Resume(value) => self.inner.begin_resume(value)?,
+        }
+
+        self.in_progress = true;
+
+        Ok(())
+    }
+}
+
+/// Future resolving once an execution step of an [`AsyncGn`] is complete
+///
+/// Returned by [`AsyncGn::try_start`], [`AsyncGn::try_start_with`] and [`AsyncGn::try_resume`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Step<'a, 'gen, 'slot, Y, R, O, F>
+where
+    O: 'gen,
+    CellSlot<Y, R, F>: 'slot,
+    F: GeneratorFlavor,
+{
+    generator: &'a mut AsyncGn<'gen, 'slot, Y, R, O, F>,
+    /// `None` once the step began
+    begin: Option<Begin<R>>,
+}
+
+// The resume value is moved out on first poll, and never pinned
+impl<'a, 'gen, 'slot, Y, R, O, F> Unpin for Step<'a, 'gen, 'slot, Y, R, O, F> where
+    F: GeneratorFlavor
+{
+}
+
+impl<'a, 'gen, 'slot, Y, R, O, F> Step<'a, 'gen, 'slot, Y, R, O, F>
+where
+    F: GeneratorFlavor,
+{
+    fn new(generator: &'a mut AsyncGn<'gen, 'slot, Y, R, O, F>, begin: Begin<R>) -> Self {
+        Self {
+            generator,
+            begin: Some(begin),
+        }
+    }
+}
+
+impl<'a, 'gen, 'slot, Y, R, O, F> Future for Step<'a, 'gen, 'slot, Y, R, O, F>
+where
+    F: GeneratorFlavor,
+{
+    type Output = Result<GnState<Y, O>, GnError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(begin) = self.begin.take() {
+            if let Err(error) = self.generator.begin(begin) {
+                return Poll::Ready(Err(error));
+            }
+        }
+
+        self.generator.poll_step(cx)
+    }
+}
+
+impl<'gen, 'slot, Y, F> Stream for AsyncGn<'gen, 'slot, Y, (), (), F>
+where
+    F: GeneratorFlavor,
+{
+    type Item = Y;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if !this.in_progress {
+            if this.is_completed() {
+                return Poll::Ready(None);
+            }
+
+            let begin = if this.started() {
+                Begin::Resume(())
+            } else {
+                Begin::Start
+            };
+
+            if let Err(error) = this.begin(begin) {
+                panic!("{error}");
+            }
+        }
+
+        match this.poll_step(cx) {
+            Poll::Ready(Ok(GnState::Suspended(value))) => Poll::Ready(Some(value)),
+            Poll::Ready(Ok(GnState::Completed(()))) => Poll::Ready(None),
+            Poll::Ready(Err(error)) => panic!("{error}"),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.yield_hint()
+    }
+}
@@ -37,10 +37,17 @@ mod stack {
             SyncRefCell::new(value)
         }
 
-        #[track_caller]
         fn cell_replace<T>(cell: &Self::Cell<T>, other: T) -> T {
             cell.replace(other)
         }
+
+        fn cell_try_replace<T>(cell: &Self::Cell<T>, other: T) -> Result<T, T> {
+            cell.try_replace(other)
+        }
+
+        fn cell_try_get<T: Copy + Default>(cell: &Self::Cell<T>) -> Option<T> {
+            cell.try_get()
+        }
     }
 
     impl StackFlavor for StackSync {}
@@ -56,11 +63,7 @@ mod stack {
             slot: &'slot StackCellSlot<Y, R>,
             generator: Pin<&'gen mut (dyn Future<Output = O> + Send + Sync + 'gen)>,
         ) -> Self {
-            Self {
-                slot,
-                generator,
-                started: false,
-            }
+            Self::from_parts(slot, generator)
         }
     }
 }
@@ -94,10 +97,17 @@ mod heap {
             SyncRefCell::new(value)
         }
 
-        #[track_caller]
         fn cell_replace<T>(cell: &Self::Cell<T>, other: T) -> T {
             cell.replace(other)
         }
+
+        fn cell_try_replace<T>(cell: &Self::Cell<T>, other: T) -> Result<T, T> {
+            cell.try_replace(other)
+        }
+
+        fn cell_try_get<T: Copy + Default>(cell: &Self::Cell<T>) -> Option<T> {
+            cell.try_get()
+        }
     }
 
     impl HeapFlavor for HeapSync {
@@ -136,9 +146,268 @@ mod heap {
     pub type StaticGn<Y, R, O> = crate::Gn<'static, 'static, Y, R, O, HeapSync>;
 }
 
+#[cfg(feature = "std")]
+pub use self::prefetch::*;
+
+#[cfg(feature = "std")]
+mod prefetch {
+    use std::any::Any;
+    use std::boxed::Box;
+    use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+    use std::string::String;
+    use std::sync::mpsc::{self, Receiver, SyncSender};
+    use std::thread;
+
+    use super::StaticGn;
+    use crate::GnState;
+
+    enum Event<Y, O> {
+        Item(Y),
+        Output(O),
+        Panic(Box<dyn Any + Send>),
+    }
+
+    /// Iterator over the values yielded by a generator running ahead on its own thread
+    ///
+    /// Returned by [`Gn::spawn_ahead`](crate::Gn::spawn_ahead).
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub struct Prefetch<Y, O> {
+        /// `None` once the generator completed or panicked
+        receiver: Option<Receiver<Event<Y, O>>>,
+        output: Option<O>,
+        worker: Option<thread::JoinHandle<()>>,
+    }
+
+    impl<Y, O> StaticGn<Y, (), O>
+    where
+        Y: Send + 'static,
+        O: Send + 'static,
+    {
+        /// Moves the generator to a new thread, where it runs ahead of the consumer
+        ///
+        /// At most `capacity` yielded values are buffered: the generator is suspended until the
+        /// consumer catches up. The returned iterator yields the values produced by the generator,
+        /// and resumes its panic if it panicked.
+        ///
+        /// ```
+        /// use genoise::sync::Gn;
+        ///
+        /// let generator = Gn::new(|mut co| async move {
+        ///     for line in ["1", "2", "3"] {
+        ///         co.suspend(line.parse::<u32>().unwrap()).await;
+        ///     }
+        ///     "done"
+        /// });
+        ///
+        /// let mut prefetch = generator.spawn_ahead(2);
+        /// assert_eq!(prefetch.by_ref().sum::<u32>(), 6);
+        /// assert_eq!(prefetch.into_output(), "done");
+        /// ```
+        ///
+        /// # Panics
+        ///
+        /// Panics if the generator was already started.
+        #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+        #[track_caller]
+        pub fn spawn_ahead(self, capacity: usize) -> Prefetch<Y, O> {
+            assert!(!self.started(), "{}", crate::GnError::AlreadyStarted);
+
+            let (sender, receiver) = mpsc::sync_channel(capacity);
+
+            let worker = thread::Builder::new()
+                .name(String::from("genoise-prefetch"))
+                .spawn(move || run_ahead(self, &sender))
+                .expect("failed to spawn the prefetch thread");
+
+            Prefetch {
+                receiver: Some(receiver),
+                output: None,
+                worker: Some(worker),
+            }
+        }
+    }
+
+    fn run_ahead<Y, O>(mut generator: StaticGn<Y, (), O>, sender: &SyncSender<Event<Y, O>>) {
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let mut state = generator.start();
+
+            loop {
+                match state {
+                    GnState::Suspended(item) => {
+                        if sender.send(Event::Item(item)).is_err() {
+                            // The consumer is gone
+                            return None;
+                        }
+
+                        state = generator.resume(());
+                    }
+                    GnState::Completed(output) => return Some(output),
+                }
+            }
+        }));
+
+        let event = match result {
+            Ok(Some(output)) => Event::Output(output),
+            Ok(None) => return,
+            Err(payload) => Event::Panic(payload),
+        };
+
+        let _ = sender.send(event);
+    }
+
+    impl<Y, O> Prefetch<Y, O> {
+        /// Returns the output of the generator, if it completed and all values were consumed
+        pub fn output(&self) -> Option<&O> {
+            self.output.as_ref()
+        }
+
+        /// Drops the remaining values, waits for the generator to complete and returns its output
+        ///
+        /// # Panics
+        ///
+        /// Resumes the panic of the generator if it panicked.
+        #[track_caller]
+        pub fn into_output(mut self) -> O {
+            while self.next().is_some() {}
+            self.output
+                .take()
+                .expect("the generator panicked before completion")
+        }
+    }
+
+    impl<Y, O> Iterator for Prefetch<Y, O> {
+        type Item = Y;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let event = self.receiver.as_ref()?.recv();
+
+            match event {
+                Ok(Event::Item(item)) => return Some(item),
+                Ok(Event::Output(output)) => self.output = Some(output),
+                Ok(Event::Panic(payload)) => {
+                    self.receiver = None;
+                    resume_unwind(payload);
+                }
+                Err(mpsc::RecvError) => {}
+            }
+
+            self.receiver = None;
+
+            None
+        }
+    }
+
+    impl<Y, O> Drop for Prefetch<Y, O> {
+        fn drop(&mut self) {
+            // Unblocks the generator if it is waiting for the consumer
+            self.receiver = None;
+
+            if let Some(worker) = self.worker.take() {
+                let _ = worker.join();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::shared::*;
+
+#[cfg(feature = "std")]
+mod shared {
+    use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+    use super::{Gn, HeapSync};
+    use crate::iter::WithOutput;
+
+    /// Handle to a generator shared between many consumers, possibly on different threads
+    ///
+    /// Each clone of the handle is an iterator pulling the next value yielded by the generator.
+    /// Execution steps are serialized, so each value is handed to exactly one consumer.
+    ///
+    /// ```
+    /// use std::thread;
+    ///
+    /// use genoise::sync::{Gn, SharedGn};
+    ///
+    /// let generator = Gn::new(|mut co| async move {
+    ///     for job in 0..100u32 {
+    ///         co.suspend(job).await;
+    ///     }
+    /// });
+    ///
+    /// let jobs = SharedGn::new(generator);
+    ///
+    /// let workers: Vec<_> = (0..4)
+    ///     .map(|_| {
+    ///         let jobs = jobs.clone();
+    ///         thread::spawn(move || jobs.sum::<u32>())
+    ///     })
+    ///     .collect();
+    ///
+    /// let total: u32 = workers.into_iter().map(|w| w.join().unwrap()).sum();
+    /// assert_eq!(total, 4950);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub struct SharedGn<'gen, 'slot, Y, O = ()>
+    where
+        O: 'gen,
+        Y: 'slot,
+    {
+        inner: Arc<Mutex<WithOutput<'gen, 'slot, Y, O, HeapSync>>>,
+    }
+
+    impl<'gen, 'slot, Y, O> SharedGn<'gen, 'slot, Y, O> {
+        /// Shares a generator between many consumers
+        pub fn new(generator: Gn<'gen, 'slot, Y, (), O>) -> Self {
+            Self {
+                inner: Arc::new(Mutex::new(generator.into_iter_with_output())),
+            }
+        }
+
+        /// Takes the output of the generator, once it completed
+        ///
+        /// Only one consumer gets the output.
+        pub fn take_output(&self) -> Option<O> {
+            self.lock().take_output()
+        }
+
+        fn lock(&self) -> MutexGuard<'_, WithOutput<'gen, 'slot, Y, O, HeapSync>> {
+            // A consumer panicking while holding the lock poisons the generator itself
+            self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    impl<'gen, 'slot, Y, O> Clone for SharedGn<'gen, 'slot, Y, O> {
+        fn clone(&self) -> Self {
+            Self {
+                inner: Arc::clone(&self.inner),
+            }
+        }
+    }
+
+    impl<'gen, 'slot, Y, O> Iterator for SharedGn<'gen, 'slot, Y, O> {
+        type Item = Y;
+
+        /// Resumes the generator, waiting for the other consumers to release it
+        ///
+        /// # Panics
+        ///
+        /// Panics if the generator panicked, including when it was resumed by another consumer.
+        fn next(&mut self) -> Option<Self::Item> {
+            self.lock().next()
+        }
+    }
+
+    impl<'gen, 'slot, Y, O> From<Gn<'gen, 'slot, Y, (), O>> for SharedGn<'gen, 'slot, Y, O> {
+        fn from(generator: Gn<'gen, 'slot, Y, (), O>) -> Self {
+            Self::new(generator)
+        }
+    }
+}
+
 // NOTE: This module is private on purpose. The `SyncRefCell` type is not part of the public API.
 #[allow(unreachable_pub)]
-mod cell {
+pub(crate) mod cell {
     use core::ops::DerefMut;
     use core::sync::atomic::{AtomicBool, Ordering};
     use core::{cell::UnsafeCell, ops::Deref};
@@ -159,28 +428,53 @@ mod cell {
     }
 
     impl<T> SyncRefCell<T> {
-        #[track_caller]
         pub(crate) fn replace(&self, other: T) -> T {
             core::mem::replace(&mut *self.borrow_mut(), other)
         }
+
+        pub(crate) fn try_replace(&self, other: T) -> Result<T, T> {
+            match self.try_borrow_mut() {
+                Some(mut value) => Ok(core::mem::replace(&mut *value, other)),
+                None => Err(other),
+            }
+        }
+
+        pub(crate) fn try_get(&self) -> Option<T>
+        where
+            T: Copy,
+        {
+            self.try_borrow_mut().map(|value| *value)
+        }
     }
 
     impl<T: ?Sized> SyncRefCell<T> {
-        #[track_caller]
+        /// Waits for the cell to be released if it is borrowed by another thread
+        ///
+        /// Borrows are never held while running user code, so the wait is short.
         pub(crate) fn borrow_mut(&self) -> SyncRefMut<'_, T> {
+            loop {
+                if let Some(value) = self.try_borrow_mut() {
+                    return value;
+                }
+
+                core::hint::spin_loop();
+            }
+        }
+
+        pub(crate) fn try_borrow_mut(&self) -> Option<SyncRefMut<'_, T>> {
             if self
                 .lock
                 .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
                 .is_ok()
             {
-                SyncRefMut {
+                Some(SyncRefMut {
                     lock: &self.lock,
                     // SAFETY: using the atomic boolean, we ensured above that there are no other
                     // references pointing to the contents of the UnsafeCell
                     value: unsafe { &mut *self.cell.get() },
-                }
+                })
             } else {
-                panic!("already borrowed");
+                None
             }
         }
     }
@@ -0,0 +1,210 @@
+//! Typestate layer over [`Gn`]
+//!
+//! An [`UnstartedGn`] is built along with the generator, and is consumed when started. Starting it
+//! hands back a [`StartedGn`] along with the first yielded value. A [`StartedGn`] is consumed on
+//! each resumption, and is handed back only if the generator suspended again. Starting a generator
+//! twice, or resuming a generator which is not started yet or which already completed is thus a
+//! compile error.
+//!
+//! ```
+//! use genoise::local::Co;
+//! use genoise::typestate::{Step, UnstartedGn};
+//!
+//! async fn double(mut co: Co<'_, u32, u32>) -> u32 {
+//!     let value = co.suspend(1).await;
+//!     value * 2
+//! }
+//!
+//! let Step::Suspended(1, generator) = UnstartedGn::new(double).start() else {
+//!     unreachable!()
+//! };
+//!
+//! let Step::Completed(4) = generator.resume(2) else {
+//!     unreachable!()
+//! };
+//! ```
+
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::future::Future;
+use core::pin::Pin;
+
+use crate::{CellSlot, GeneratorFlavor, Gn, GnState};
+
+/// The result of a [`StartedGn`] execution
+pub enum Step<'gen, 'slot, Y, R, O, F>
+where
+    O: 'gen,
+    CellSlot<Y, R, F>: 'slot,
+    F: GeneratorFlavor,
+{
+    /// The generator yielded a value, and can be resumed using the [`StartedGn`] handle
+    Suspended(Y, StartedGn<'gen, 'slot, Y, R, O, F>),
+    /// The generator completed, returning its final value
+    Completed(O),
+}
+
+impl<'gen, 'slot, Y, R, O, F> fmt::Debug for Step<'gen, 'slot, Y, R, O, F>
+where
+    Y: fmt::Debug,
+    O: fmt::Debug,
+    F: GeneratorFlavor,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Suspended(yielded, _) => write!(f, "Suspended({yielded:?}, ..)"),
+            Step::Completed(returned) => write!(f, "Completed({returned:?})"),
+        }
+    }
+}
+
+impl<'gen, 'slot, Y, R, O, F> Step<'gen, 'slot, Y, R, O, F>
+where
+    F: GeneratorFlavor,
+{
+    fn from_state(state: GnState<Y, O>, inner: Gn<'gen, 'slot, Y, R, O, F>) -> Self {
+        match state {
+            GnState::Suspended(yielded) => Step::Suspended(yielded, StartedGn { inner }),
+            GnState::Completed(returned) => Step::Completed(returned),
+        }
+    }
+}
+
+/// A generator which was started and did not complete yet
+#[must_use = "generators do nothing unless you `.resume(…)` them"]
+pub struct StartedGn<'gen, 'slot, Y, R, O, F>
+where
+    O: 'gen,
+    CellSlot<Y, R, F>: 'slot,
+    F: GeneratorFlavor,
+{
+    inner: Gn<'gen, 'slot, Y, R, O, F>,
+}
+
+impl<'gen, 'slot, Y, R, O, F> StartedGn<'gen, 'slot, Y, R, O, F>
+where
+    F: GeneratorFlavor,
+{
+    /// Resumes execution of the generator, passing in a value
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator does not follow the yield and resume protocol.
+    #[track_caller]
+    pub fn resume(mut self, value: R) -> Step<'gen, 'slot, Y, R, O, F> {
+        match self.inner.resume_started(value) {
+            Ok(state) => Step::from_state(state, self.inner),
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Returns the underlying generator, opting out of the typestate API
+    pub fn into_inner(self) -> Gn<'gen, 'slot, Y, R, O, F> {
+        self.inner
+    }
+}
+
+/// A generator which was not started yet
+#[must_use = "generators do nothing unless you `.start()` them"]
+pub struct UnstartedGn<'gen, 'slot, Y, R, O, F>
+where
+    O: 'gen,
+    CellSlot<Y, R, F>: 'slot,
+    F: GeneratorFlavor,
+{
+    inner: Gn<'gen, 'slot, Y, R, O, F>,
+}
+
+impl<'gen, 'slot, Y, R, O, F> UnstartedGn<'gen, 'slot, Y, R, O, F>
+where
+    F: GeneratorFlavor,
+{
+    /// Builds a generator from its parts, see [`Gn::from_parts`]
+    pub fn from_parts(
+        slot: F::SharedPtr<'slot, CellSlot<Y, R, F>>,
+        generator: Pin<F::UniquePtr<'gen, F::Fut<'gen, O>>>,
+    ) -> Self {
+        Self {
+            inner: Gn::from_parts(slot, generator),
+        }
+    }
+
+    /// Starts execution of the generator, consuming it
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator does not follow the yield and resume protocol.
+    #[track_caller]
+    pub fn start(mut self) -> Step<'gen, 'slot, Y, R, O, F> {
+        match self.inner.try_start() {
+            Ok(state) => Step::from_state(state, self.inner),
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Starts execution of the generator, passing in a first value
+    ///
+    /// See [`Gn::start_with`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator does not follow the yield and resume protocol.
+    #[track_caller]
+    pub fn start_with(mut self, value: R) -> Step<'gen, 'slot, Y, R, O, F> {
+        match self.inner.try_start_with(value) {
+            Ok(state) => Step::from_state(state, self.inner),
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Returns the underlying generator, opting out of the typestate API
+    pub fn into_inner(self) -> Gn<'gen, 'slot, Y, R, O, F> {
+        self.inner
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'gen, 'slot, Y, R, O> UnstartedGn<'gen, 'slot, Y, R, O, crate::local::HeapLocal> {
+    /// Creates a local generator, see [`local::Gn::new`](crate::local::Gn::new)
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn new<Producer, Generator>(producer: Producer) -> Self
+    where
+        Producer: FnOnce(crate::local::Co<'slot, Y, R>) -> Generator,
+        Generator: Future<Output = O> + 'gen,
+    {
+        Self {
+            inner: crate::local::Gn::new(producer),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'gen, 'slot, Y, R, O> UnstartedGn<'gen, 'slot, Y, R, O, crate::send::HeapSend> {
+    /// Creates a generator which can be sent to another thread, see
+    /// [`send::Gn::new`](crate::send::Gn::new)
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn new_send<Producer, Generator>(producer: Producer) -> Self
+    where
+        Producer: FnOnce(crate::send::Co<'slot, Y, R>) -> Generator,
+        Generator: Future<Output = O> + Send + 'gen,
+    {
+        Self {
+            inner: crate::send::Gn::new(producer),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'gen, 'slot, Y, R, O> UnstartedGn<'gen, 'slot, Y, R, O, crate::sync::HeapSync> {
+    /// Creates a thread safe generator, see [`sync::Gn::new`](crate::sync::Gn::new)
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn new_sync<Producer, Generator>(producer: Producer) -> Self
+    where
+        Producer: FnOnce(crate::sync::Co<'slot, Y, R>) -> Generator,
+        Generator: Future<Output = O> + Send + Sync + 'gen,
+    {
+        Self {
+            inner: crate::sync::Gn::new(producer),
+        }
+    }
+}
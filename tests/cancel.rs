@@ -0,0 +1,70 @@
+use genoise::{local, sync, Cancelled, Co, GeneratorFlavor, GnError, GnState};
+
+#[derive(Debug, PartialEq)]
+enum Message {
+    Data(u8),
+    Goodbye,
+}
+
+async fn session<F: GeneratorFlavor>(mut co: Co<'_, Message, (), F>) -> Result<u8, Cancelled> {
+    let mut sent = 0;
+
+    for i in 0..3 {
+        if let Err(Cancelled) = co.suspend_cancellable(Message::Data(i)).await {
+            co.suspend(Message::Goodbye).await;
+            return Err(Cancelled);
+        }
+
+        sent += 1;
+    }
+
+    Ok(sent)
+}
+
+#[test]
+fn check_cancellation_runs_cleanup_local() {
+    local::let_gen!(g, session);
+    assert!(matches!(g.start(), GnState::Suspended(Message::Data(0))));
+    assert!(matches!(g.cancel(), GnState::Suspended(Message::Goodbye)));
+    assert!(matches!(g.resume(()), GnState::Completed(Err(Cancelled))));
+    assert!(g.is_completed());
+}
+
+#[test]
+fn check_cancellation_runs_cleanup_sync() {
+    let mut g = sync::Gn::new(session);
+    assert!(matches!(g.start(), GnState::Suspended(Message::Data(0))));
+    assert!(matches!(g.resume(()), GnState::Suspended(Message::Data(1))));
+    assert!(matches!(g.cancel(), GnState::Suspended(Message::Goodbye)));
+    assert!(matches!(g.resume(()), GnState::Completed(Err(Cancelled))));
+}
+
+#[test]
+fn check_cancellation_of_non_cancellable_suspension() {
+    let mut g = local::Gn::new(|mut co: local::Co<'_, u8, ()>| async move {
+        co.suspend(0).await;
+        co.suspend(1).await;
+    });
+
+    assert!(matches!(g.start(), GnState::Suspended(0)));
+    assert!(matches!(g.cancel(), GnState::Completed(Err(Cancelled))));
+    assert!(g.is_completed());
+}
+
+#[test]
+fn check_cancellation_may_complete_with_output() {
+    let mut g = local::Gn::new(|mut co: local::Co<'_, u8, ()>| async move {
+        co.suspend_cancellable(0).await.map(|()| 1).unwrap_or(2)
+    });
+
+    assert!(matches!(g.start(), GnState::Suspended(0)));
+    assert!(matches!(g.cancel(), GnState::Completed(Ok(2))));
+}
+
+#[test]
+fn check_cancellation_before_start() {
+    let mut g = local::Gn::new(session);
+    assert!(matches!(g.cancel(), GnState::Completed(Err(Cancelled))));
+    assert_eq!(g.try_resume(()).unwrap_err(), GnError::Completed);
+    assert_eq!(g.try_cancel().unwrap_err(), GnError::Completed);
+}
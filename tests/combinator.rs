@@ -0,0 +1,90 @@
+use genoise::{local, sync, Co, Generator as _, GeneratorFlavor, GnError, GnState};
+
+async fn accumulate<F: GeneratorFlavor>(mut co: Co<'_, u32, u32, F>) -> u32 {
+    let mut total = 0;
+    for _ in 0..3 {
+        total += co.suspend(total).await;
+    }
+    total
+}
+
+#[test]
+fn check_map_yield_and_output() {
+    let mut generator = local::Gn::new(accumulate)
+        .map_yield(|total| total % 2 == 0)
+        .map_output(|total| total.to_string());
+    assert!(matches!(generator.start(), GnState::Suspended(true)));
+    assert!(matches!(generator.resume(3), GnState::Suspended(false)));
+    assert!(matches!(generator.resume(4), GnState::Suspended(false)));
+    assert!(matches!(generator.resume(5), GnState::Completed(total) if total == "12"));
+    assert!(generator.is_completed());
+}
+
+#[test]
+fn check_map_resume_stack() {
+    local::let_gen!(generator, accumulate);
+    let mut generator = generator.map_resume(|value: &str| value.len() as u32);
+    assert!(matches!(generator.start(), GnState::Suspended(0u32)));
+    assert!(matches!(generator.resume("abc"), GnState::Suspended(3u32)));
+    assert!(matches!(generator.resume(""), GnState::Suspended(3u32)));
+    assert!(matches!(generator.resume("ab"), GnState::Completed(5u32)));
+}
+
+async fn numbers<F: GeneratorFlavor>(mut co: Co<'_, u32, (), F>) -> usize {
+    for i in 0..6 {
+        co.suspend(i).await;
+    }
+    6
+}
+
+#[test]
+fn check_filter_map_yield() {
+    let mut generator = sync::Gn::new(numbers).filter_map_yield(|i| (i % 3 == 0).then_some(i * 2));
+    assert!(matches!(generator.start(), GnState::Suspended(0)));
+    assert!(matches!(generator.resume(()), GnState::Suspended(6)));
+    assert!(matches!(generator.resume(()), GnState::Completed(6)));
+}
+
+#[test]
+fn check_iterator_filter_map_is_not_shadowed() {
+    let generator = local::Gn::new(|mut co: local::Co<'_, u32, ()>| async move {
+        for i in 0..6 {
+            co.suspend(i).await;
+        }
+    });
+
+    let even: Vec<u32> = generator
+        .filter_map(|i| (i % 2 == 0).then_some(i * 10))
+        .collect();
+    assert_eq!(even, [0, 20, 40]);
+}
+
+#[test]
+fn check_then() {
+    let mut generator = local::Gn::new(numbers)
+        .filter_map_yield(|i| (i > 3).then_some(i))
+        .then(|count| {
+            local::Gn::new(move |mut co: local::Co<'_, u32, ()>| async move {
+                co.suspend(count as u32 * 100).await;
+                "done"
+            })
+        });
+    assert!(!generator.started());
+    assert!(matches!(generator.start(), GnState::Suspended(4)));
+    assert!(matches!(generator.resume(()), GnState::Suspended(5)));
+    assert!(matches!(generator.resume(()), GnState::Suspended(600)));
+    assert!(!generator.is_completed());
+    assert!(matches!(generator.resume(()), GnState::Completed("done")));
+    assert!(generator.is_completed());
+    assert_eq!(generator.try_resume(()).unwrap_err(), GnError::Completed);
+}
+
+#[test]
+fn check_adapter_by_mut_ref() {
+    let mut generator = local::Gn::new(accumulate);
+    let mut adapter = (&mut generator).map_yield(|total| total + 1);
+    assert!(matches!(adapter.start(), GnState::Suspended(1)));
+    assert!(matches!(adapter.resume(10), GnState::Suspended(11)));
+    assert!(matches!(generator.resume(1), GnState::Suspended(11)));
+    assert_eq!(generator.try_start().unwrap_err(), GnError::AlreadyStarted);
+}
@@ -31,13 +31,18 @@ fn check_impl(source_file: &Path, diffing_program: Option<&DiffingProgram>, over
 
     for line in String::from_utf8_lossy(&output.stderr).lines() {
         // ignore source file paths
-        if line.starts_with("   -->") {
+        if line.trim_start().starts_with("-->") {
+            continue;
+        }
+
+        // ignore paths to files holding long type names, as they contain a hash
+        if line.contains("the full name for the type has been written to") {
             continue;
         }
 
         // filter out line numbers to prevent test from failing when modifying source code
         line.chars().fold(true, |filter, c| {
-            if filter && c.is_numeric() {
+            if filter && (c.is_numeric() || c == ' ') {
                 stderr.push(' ');
                 true
             } else {
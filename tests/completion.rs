@@ -0,0 +1,55 @@
+use core::future::{poll_fn, Future};
+use std::rc::Rc;
+
+use genoise::{local, GnState};
+
+async fn countdown(mut co: local::Co<'_, u8, ()>) {
+    for i in (1..=3).rev() {
+        co.suspend(i).await;
+    }
+}
+
+#[test]
+fn check_is_completed() {
+    let mut g = local::Gn::new(countdown);
+    assert!(!g.is_completed());
+    assert_eq!(g.by_ref().count(), 3);
+    assert!(g.is_completed());
+}
+
+#[test]
+fn check_iterator_is_fused() {
+    let mut g = local::Gn::new(countdown);
+    assert_eq!(g.by_ref().collect::<Vec<_>>(), [3, 2, 1]);
+    assert_eq!(g.next(), None);
+    assert_eq!(g.next(), None);
+}
+
+#[test]
+#[should_panic(expected = "generator was resumed after completion")]
+fn check_resume_after_completion_panics() {
+    let mut g = local::Gn::new(|_: local::Co<'_, (), ()>| async {});
+    assert!(matches!(g.start(), GnState::Completed(())));
+    g.resume(());
+}
+
+#[test]
+fn check_future_is_dropped_on_completion() {
+    let resource = Rc::new(());
+
+    let mut g = local::Gn::new(|mut co: local::Co<'_, (), ()>| {
+        let resource = Rc::clone(&resource);
+        let mut body = Box::pin(async move { co.suspend(()).await });
+
+        // The resource is released only once the future itself is dropped
+        poll_fn(move |cx| {
+            let _keep_alive = &resource;
+            body.as_mut().poll(cx)
+        })
+    });
+
+    assert!(matches!(g.start(), GnState::Suspended(())));
+    assert_eq!(Rc::strong_count(&resource), 2);
+    assert!(matches!(g.resume(()), GnState::Completed(())));
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
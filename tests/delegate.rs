@@ -0,0 +1,123 @@
+use genoise::{local, sync, Co, GeneratorFlavor, GnState};
+
+async fn yield_all<F: GeneratorFlavor>(mut co: Co<'_, u8, (), F>, values: &[u8]) {
+    co.suspend(0).await;
+    co.suspend_iter(values.iter().copied()).await;
+    co.suspend(u8::MAX).await;
+}
+
+#[test]
+fn check_suspend_iter_local() {
+    local::let_gen!(generator, |co| { yield_all(co, &[1, 2, 3]) });
+    assert!(generator.eq([0, 1, 2, 3, u8::MAX]));
+}
+
+#[test]
+fn check_suspend_iter_sync() {
+    let generator = sync::Gn::new(|co| yield_all(co, &[]));
+    assert!(generator.eq([0, u8::MAX]));
+}
+
+async fn ask_all<F: GeneratorFlavor>(mut co: Co<'_, &'static str, bool, F>) -> Vec<bool> {
+    co.suspend_iter_collect(["first", "second", "third"]).await
+}
+
+#[test]
+fn check_suspend_iter_collect() {
+    let mut generator = local::Gn::new(ask_all);
+    assert!(matches!(generator.start(), GnState::Suspended("first")));
+    assert!(matches!(
+        generator.resume(true),
+        GnState::Suspended("second")
+    ));
+    assert!(matches!(
+        generator.resume(false),
+        GnState::Suspended("third")
+    ));
+
+    let GnState::Completed(answers) = generator.resume(true) else {
+        panic!()
+    };
+    assert_eq!(answers, [true, false, true]);
+}
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Login(u32),
+    Fetch(u32),
+}
+
+#[derive(Debug)]
+enum Response {
+    Accepted(bool),
+    Payload(Vec<u8>),
+}
+
+async fn login<F: GeneratorFlavor>(mut co: Co<'_, u32, bool, F>) -> bool {
+    co.suspend(42).await
+}
+
+async fn fetch<Y, R, F, MY, MR>(
+    co: &mut genoise::MappedCo<'_, '_, Y, R, F, u32, Vec<u8>, MY, MR>,
+) -> usize
+where
+    F: GeneratorFlavor,
+    Y: Unpin,
+    MY: FnMut(u32) -> Y,
+    MR: FnMut(R) -> Vec<u8>,
+{
+    co.suspend(1).await.len() + co.suspend(2).await.len()
+}
+
+async fn protocol<F: GeneratorFlavor>(mut co: Co<'_, Event, Response, F>) -> Option<usize> {
+    let accepted = co
+        .suspend_from_mapped(sync::Gn::new(login), Event::Login, |response| {
+            matches!(response, Response::Accepted(true))
+        })
+        .await;
+
+    if !accepted {
+        return None;
+    }
+
+    let mut fetch_co = co.map(Event::Fetch, |response| match response {
+        Response::Payload(payload) => payload,
+        Response::Accepted(_) => Vec::new(),
+    });
+
+    Some(fetch(&mut fetch_co).await)
+}
+
+#[test]
+fn check_suspend_from_mapped() {
+    let mut generator = sync::Gn::new(protocol);
+    assert!(matches!(
+        generator.start(),
+        GnState::Suspended(Event::Login(42))
+    ));
+    assert!(matches!(
+        generator.resume(Response::Accepted(false)),
+        GnState::Completed(None)
+    ));
+}
+
+#[test]
+fn check_mapped_co() {
+    local::let_gen!(generator, protocol);
+    assert!(matches!(
+        generator.start(),
+        GnState::Suspended(Event::Login(42))
+    ));
+    assert!(matches!(
+        generator.resume(Response::Accepted(true)),
+        GnState::Suspended(Event::Fetch(1))
+    ));
+    assert!(matches!(
+        generator.resume(Response::Payload(vec![0; 3])),
+        GnState::Suspended(Event::Fetch(2))
+    ));
+    assert!(matches!(
+        generator.resume(Response::Payload(vec![0; 4])),
+        GnState::Completed(Some(7usize))
+    ));
+}
@@ -0,0 +1,101 @@
+use std::thread;
+
+use genoise::drive::{Driver, Handler};
+use genoise::{local, sync, Co, Generator as _, GeneratorFlavor};
+
+use crate::util::{block_on, Delayed};
+
+async fn ask<F: GeneratorFlavor>(mut co: Co<'_, &'static str, usize, F>) -> usize {
+    let mut total = 0;
+    for question in ["a", "bb", "ccc"] {
+        total += co.suspend(question).await;
+    }
+    total
+}
+
+struct Recorder {
+    seen: Vec<&'static str>,
+}
+
+impl Handler<&'static str, usize> for Recorder {
+    fn handle(&mut self, yielded: &'static str) -> usize {
+        self.seen.push(yielded);
+        yielded.len()
+    }
+}
+
+async fn double<F: GeneratorFlavor>(mut co: Co<'_, u32, u32, F>) -> u32 {
+    co.suspend(1).await + co.suspend(2).await
+}
+
+#[test]
+fn check_run_with_closure() {
+    assert_eq!(local::Gn::new(ask).run_with(str::len), 6);
+    assert_eq!(sync::Gn::new(double).run_with(|value| value * 2), 6);
+}
+
+#[test]
+fn check_run_with_handler() {
+    local::let_gen!(generator, ask);
+    let mut driver = Driver::new(&mut generator, Recorder { seen: Vec::new() });
+    assert_eq!(driver.step_n(10), Some(6usize));
+
+    let (_, recorder) = driver.into_inner();
+    assert_eq!(recorder.seen, ["a", "bb", "ccc"]);
+    assert!(generator.is_completed());
+}
+
+#[test]
+fn check_step_n_and_run_until() {
+    let mut driver = local::Gn::new(ask).drive(|question: &str| question.len() * 2);
+    assert_eq!(driver.pending(), None);
+    assert_eq!(driver.step_n(1), None);
+    assert_eq!(driver.pending(), Some(&"a"));
+    assert_eq!(driver.run_until(|question| question.len() == 3), None);
+    assert_eq!(driver.pending(), Some(&"ccc"));
+    assert_eq!(driver.run_until(|_| true), Some(12));
+}
+
+#[test]
+#[should_panic = "generator was resumed after completion"]
+fn check_step_after_completion() {
+    let mut driver = local::Gn::new(ask).drive(|_| 0);
+    assert_eq!(driver.step_n(4), Some(0));
+    driver.step();
+}
+
+#[test]
+#[should_panic = "generator was already started"]
+fn check_drive_started() {
+    let mut generator = local::Gn::new(ask);
+    generator.start();
+    let _ = generator.run_with(|_| 0);
+}
+
+#[test]
+fn check_drive_async() {
+    let output = block_on(
+        local::Gn::new(ask)
+            .drive_async(|question| async move { Delayed::new(question).await.len() }),
+    );
+    assert_eq!(output, 6);
+
+    local::let_gen!(generator, ask);
+    let output = block_on(genoise::drive::drive_async(&mut generator, |_| {
+        Delayed::new(1)
+    }));
+    assert_eq!(output, 3usize);
+}
+
+#[test]
+fn check_drive_async_is_send() {
+    fn assert_send<T: Send>(value: T) -> T {
+        value
+    }
+
+    let future = assert_send(
+        sync::Gn::new(double).drive_async(|value| async move { Delayed::new(value).await + 1 }),
+    );
+    let output = thread::spawn(move || block_on(future)).join().unwrap();
+    assert_eq!(output, 5);
+}
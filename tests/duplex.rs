@@ -0,0 +1,113 @@
+use genoise::duplex::{self, DuplexError, Message, Turn};
+use genoise::{local, sync, Co, GeneratorFlavor, GnError};
+
+async fn count_down<F: GeneratorFlavor>(mut co: Co<'_, Turn<u8>, Option<u8>, F>) -> u8 {
+    let mut received = 0;
+    let mut value = 3;
+    while value > 0 {
+        if co.suspend(Turn::Send(value)).await.is_some() {
+            received += 1;
+        }
+        value -= 1;
+    }
+    received
+}
+
+async fn echo<F: GeneratorFlavor>(mut co: Co<'_, Turn<u8>, Option<u8>, F>) {
+    let mut received = co.suspend(Turn::Waiting).await;
+    while let Some(value) = received {
+        received = co.suspend(Turn::Send(value * 10)).await;
+    }
+}
+
+#[test]
+fn check_run() {
+    let mut log = Vec::new();
+    let outputs =
+        duplex::run(
+            local::Gn::new(count_down),
+            sync::Gn::new(echo),
+            |message| match message {
+                Message::Initiator(value) => log.push(i32::from(*value)),
+                Message::Responder(value) => log.push(-i32::from(*value)),
+            },
+        );
+    assert_eq!(outputs, Ok((3, ())));
+    assert_eq!(log, [3, -30, 2, -20, 1, -10]);
+}
+
+#[test]
+fn check_run_stack() {
+    local::let_gen!(initiator, echo);
+    local::let_gen!(responder, echo);
+    let outputs = duplex::run(&mut initiator, &mut responder, |_| {});
+    assert_eq!(outputs, Err(DuplexError::Deadlock));
+    assert!(!responder.is_completed());
+}
+
+async fn silent<F: GeneratorFlavor>(mut co: Co<'_, Turn<u8>, Option<u8>, F>) {
+    co.suspend(Turn::Waiting).await;
+}
+
+#[test]
+fn check_deadlock_on_completed_peer() {
+    let outputs = duplex::run(
+        local::Gn::new(|_: local::Co<'_, Turn<u8>, Option<u8>>| async {}),
+        local::Gn::new(silent),
+        |_| {},
+    );
+    assert_eq!(outputs, Err(DuplexError::Deadlock));
+}
+
+#[test]
+fn check_responder_sent_first() {
+    let outputs = duplex::run(
+        local::Gn::new(count_down),
+        local::Gn::new(count_down),
+        |_| {},
+    );
+    assert_eq!(outputs, Err(DuplexError::ResponderSentFirst));
+}
+
+#[test]
+fn check_generator_error() {
+    let mut started = local::Gn::new(echo);
+    started.start();
+    let outputs = duplex::run(local::Gn::new(count_down), started, |_| {});
+    assert_eq!(
+        outputs,
+        Err(DuplexError::Generator(GnError::AlreadyStarted))
+    );
+}
+
+#[test]
+fn check_initiator_sent_to_completed_responder() {
+    let mut log = Vec::new();
+    let outputs = duplex::run(
+        local::Gn::new(count_down),
+        local::Gn::new(silent),
+        |message| log.push(message == Message::Initiator(&2)),
+    );
+    assert_eq!(outputs, Err(DuplexError::PeerCompleted));
+    assert_eq!(log, [false, true]);
+}
+
+async fn send_once<F: GeneratorFlavor>(mut co: Co<'_, Turn<u8>, Option<u8>, F>) {
+    co.suspend(Turn::Send(1)).await;
+}
+
+async fn reply_twice<F: GeneratorFlavor>(mut co: Co<'_, Turn<u8>, Option<u8>, F>) {
+    let received = co.suspend(Turn::Waiting).await.unwrap_or_default();
+    co.suspend(Turn::Send(received * 10)).await;
+    co.suspend(Turn::Send(received * 20)).await;
+}
+
+#[test]
+fn check_responder_sent_to_completed_initiator() {
+    let outputs = duplex::run(
+        local::Gn::new(send_once),
+        local::Gn::new(reply_twice),
+        |_| {},
+    );
+    assert_eq!(outputs, Err(DuplexError::PeerCompleted));
+}
@@ -0,0 +1,101 @@
+use genoise::effect::{Dispatcher, Effect, Request, Response};
+use genoise::{local, Co, Generator as _, GeneratorFlavor, GnState};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq)]
+struct Get(&'static str);
+
+impl Effect for Get {
+    type Response = Option<u32>;
+}
+
+struct Log(String);
+
+impl Effect for Log {
+    type Response = ();
+}
+
+async fn program<F: GeneratorFlavor>(mut co: Co<'_, Request, Response, F>) -> u32 {
+    let a = co.perform(Get("a")).await.unwrap_or_default();
+    let b = co.perform(Get("b")).await.unwrap_or_default();
+    co.perform(Log(format!("{a} + {b}"))).await;
+    a + b
+}
+
+#[test]
+fn check_dispatcher() {
+    let mut logs = Vec::new();
+    let dispatcher = Dispatcher::new()
+        .on(|Get(key)| (key == "a").then_some(5))
+        .on(|Log(message)| logs.push(message));
+    assert_eq!(local::Gn::new(program).run_with(dispatcher), 5);
+    assert_eq!(logs, ["5 + 0"]);
+}
+
+#[test]
+fn check_dispatcher_replaces_handler() {
+    let mut dispatcher = Dispatcher::new()
+        .on(|Get(_)| None)
+        .on(|Get(_)| Some(1))
+        .on(|Log(_)| ());
+    local::let_gen!(generator, program);
+    assert_eq!(
+        generator.run_with(&mut |request| dispatcher.dispatch(request)),
+        2u32
+    );
+}
+
+#[test]
+fn check_manual_dispatch() {
+    let mut generator = local::Gn::new(program);
+    let GnState::Suspended(request) = generator.start() else {
+        panic!("not suspended")
+    };
+    assert!(request.is::<Get>());
+    assert!(!request.is::<Log>());
+
+    let request = request.handle(|Log(_)| ()).unwrap_err();
+    let response = request.handle(|Get(key)| Some(key.len() as u32)).unwrap();
+    assert!(matches!(generator.resume(response), GnState::Suspended(_)));
+}
+
+#[test]
+fn check_try_dispatch_unhandled() {
+    let mut dispatcher = Dispatcher::new().on(|Log(_)| ());
+    let request = dispatcher.try_dispatch(Request::new(Get("a"))).unwrap_err();
+    assert_eq!(
+        format!("{request:?}"),
+        "Request(\"genoise_tests::effect::Get\")"
+    );
+}
+
+#[test]
+#[should_panic = "expected a response to `genoise_tests::effect::Get`, got a response to `genoise_tests::effect::Log`"]
+fn check_response_type_mismatch() {
+    let mut generator = local::Gn::new(program);
+    generator.start();
+    generator.resume(Response::new::<Log>(()));
+}
+
+#[test]
+#[should_panic = "no handler registered for `genoise_tests::effect::Log`"]
+fn check_missing_handler() {
+    local::Gn::new(program).run_with(Dispatcher::new().on(|Get(_)| None));
+}
+
+struct Share(Rc<str>);
+
+impl Effect for Share {
+    type Response = Rc<str>;
+}
+
+#[test]
+fn check_effect_not_thread_safe() {
+    async fn share(mut co: local::Co<'_, Request, Response>) -> usize {
+        let shared = co.perform(Share(Rc::from("shared"))).await;
+        Rc::strong_count(&shared)
+    }
+
+    let dispatcher = Dispatcher::new().on(|Share(shared)| Rc::clone(&shared));
+    assert_eq!(local::Gn::new(share).run_with(dispatcher), 1);
+}
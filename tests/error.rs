@@ -0,0 +1,54 @@
+use core::future::{poll_fn, Future};
+use core::pin::pin;
+
+use genoise::{local, Co, GeneratorFlavor, GnError, GnState};
+
+async fn generator<F: GeneratorFlavor>(mut co: Co<'_, u8, u8, F>) -> u8 {
+    co.suspend(1).await + 1
+}
+
+#[test]
+fn check_resume_before_start_is_an_error() {
+    local::let_gen!(g, generator);
+    assert_eq!(g.try_resume(0).unwrap_err(), GnError::NotStarted);
+}
+
+#[test]
+fn check_start_twice_is_an_error() {
+    local::let_gen!(g, generator);
+    assert!(matches!(g.try_start(), Ok(GnState::Suspended(1))));
+    assert_eq!(g.try_start().unwrap_err(), GnError::AlreadyStarted);
+}
+
+#[test]
+fn check_resume_after_completion_is_an_error() {
+    let mut g = local::Gn::new(generator);
+    assert!(matches!(g.try_start(), Ok(GnState::Suspended(1))));
+    assert!(matches!(g.try_resume(1), Ok(GnState::Completed(2))));
+    assert_eq!(g.try_resume(2).unwrap_err(), GnError::Completed);
+}
+
+#[test]
+#[should_panic(expected = "generator must be started before it can be resumed")]
+fn check_resume_before_start_panics() {
+    local::let_gen!(g, generator);
+    g.resume(0);
+}
+
+#[test]
+fn check_interrupt_polled_without_resume_value_is_an_error() {
+    async fn interleaved(mut co: local::Co<'_, u8, u8>) {
+        let mut first = pin!(co.suspend(1));
+        let mut second = pin!(co.suspend(2));
+
+        poll_fn(|cx| {
+            let _ = first.as_mut().poll(cx);
+            second.as_mut().poll(cx)
+        })
+        .await;
+    }
+
+    let mut g = local::Gn::new(interleaved);
+    assert!(matches!(g.try_start(), Ok(GnState::Suspended(2))));
+    assert_eq!(g.try_resume(0).unwrap_err(), GnError::SlotProtocol);
+}
@@ -0,0 +1,64 @@
+use core::future::{pending, ready};
+
+use genoise::{local, sync, Co, GeneratorFlavor, GnError, GnState};
+
+use crate::util::YieldNow;
+
+async fn add_one(value: u8) -> u8 {
+    value + 1
+}
+
+async fn suspend_twice<F: GeneratorFlavor>(co: &mut Co<'_, u8, u8, F>, value: u8) -> u8 {
+    let value = co.suspend(value).await;
+    co.suspend(value).await
+}
+
+async fn immediate_futures<F: GeneratorFlavor>(mut co: Co<'_, u8, u8, F>) -> u8 {
+    let value = ready(1).await;
+    let value = add_one(value).await;
+    let value = co.suspend(value).await;
+    suspend_twice(&mut co, value).await
+}
+
+#[test]
+fn check_immediate_futures_are_supported_local() {
+    local::let_gen!(g, immediate_futures);
+    assert!(matches!(g.start(), GnState::Suspended(2u8)));
+    assert!(matches!(g.resume(3), GnState::Suspended(3)));
+    assert!(matches!(g.resume(4), GnState::Suspended(4)));
+    assert!(matches!(g.resume(5), GnState::Completed(5u8)));
+}
+
+#[test]
+fn check_immediate_futures_are_supported_sync() {
+    let mut g = sync::Gn::new(immediate_futures);
+    assert!(matches!(g.start(), GnState::Suspended(2)));
+    assert!(matches!(g.resume(3), GnState::Suspended(3)));
+    assert!(matches!(g.resume(4), GnState::Suspended(4)));
+    assert!(matches!(g.resume(5), GnState::Completed(5u8)));
+}
+
+#[test]
+fn check_pending_future_is_reported_on_start() {
+    let mut g = local::Gn::new(|_: local::Co<'_, (), ()>| pending::<()>());
+    assert_eq!(g.try_start().unwrap_err(), GnError::ForeignFuture);
+}
+
+#[test]
+fn check_pending_future_is_reported_on_resume() {
+    let mut g = sync::Gn::new(|mut co: sync::Co<'_, u8, u8>| async move {
+        let value = co.suspend(0).await;
+        YieldNow::new().await;
+        co.suspend(value).await
+    });
+
+    assert!(matches!(g.try_start(), Ok(GnState::Suspended(0))));
+    assert_eq!(g.try_resume(1).unwrap_err(), GnError::ForeignFuture);
+}
+
+#[test]
+#[should_panic(expected = "generator is waiting on a future other than `Interrupt`")]
+fn check_pending_future_panics() {
+    let mut g = local::Gn::new(|_: local::Co<'_, (), ()>| YieldNow::new());
+    let _ = g.start();
+}
@@ -0,0 +1,113 @@
+use genoise::{local, sync, Co, GeneratorFlavor, GnState};
+
+async fn parse_digits<F: GeneratorFlavor>(
+    mut co: Co<'_, u32, (), F>,
+    input: &str,
+) -> Result<(), char> {
+    for c in input.chars() {
+        let digit = c.to_digit(10).ok_or(c)?;
+        co.suspend(digit).await;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn check_iter_with_output_local() {
+    let mut iter = local::Gn::new(|co| parse_digits(co, "12a3")).into_iter_with_output();
+    assert_eq!(iter.output(), None);
+    assert_eq!(iter.by_ref().sum::<u32>(), 3);
+    assert_eq!(iter.output(), Some(&Err('a')));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.into_output(), Some(Err('a')));
+}
+
+#[test]
+fn check_iter_with_output_stack() {
+    local::let_gen!(generator, |co| { parse_digits(co, "123") });
+    let mut iter = generator.into_iter_with_output();
+    assert!(iter.by_ref().eq([1, 2, 3]));
+    assert_eq!(iter.output(), Some(&Ok(())));
+}
+
+#[test]
+fn check_collect_with_output() {
+    let (digits, output): (Vec<u32>, _) =
+        sync::Gn::new(|co| parse_digits(co, "42")).collect_with_output();
+    assert_eq!(digits, [4, 2]);
+    assert_eq!(output, Ok(()));
+}
+
+#[test]
+fn check_collect_with_output_of_started_generator() {
+    let mut generator = local::Gn::new(|co| parse_digits(co, "42x"));
+    let _ = generator.start();
+    let (digits, output): (Vec<u32>, _) = generator.collect_with_output();
+    assert_eq!(digits, [2]);
+    assert_eq!(output, Err('x'));
+}
+
+async fn countdown<F: GeneratorFlavor>(mut co: Co<'_, usize, bool, F>, mut count: usize) -> usize {
+    let mut skipped = 0;
+
+    while count > 0 {
+        if !co.suspend(count).await {
+            skipped += 1;
+        }
+
+        count -= 1;
+    }
+
+    skipped
+}
+
+#[test]
+fn check_iter_resume_default() {
+    let mut iter = local::Gn::new(|co| countdown(co, 3)).iter_resume_default();
+    assert!(iter.by_ref().eq([3, 2, 1]));
+    assert_eq!(iter.output(), Some(&3));
+}
+
+#[test]
+fn check_iter_resume_cloned() {
+    local::let_gen!(generator, |co| { countdown(co, 2) });
+    let mut iter = generator.iter_resume_cloned(true);
+    assert!(iter.by_ref().eq([2, 1]));
+    assert_eq!(iter.into_output(), Some(0usize));
+}
+
+#[test]
+fn check_iter_resume_with() {
+    let mut iter = sync::Gn::new(|co| countdown(co, 4)).iter_resume_with(|count| count % 2 == 0);
+    assert!(iter.by_ref().eq([4, 3, 2, 1]));
+    assert_eq!(iter.output(), Some(&2));
+}
+
+#[test]
+fn check_feed() {
+    let mut iter = local::Gn::new(|co| countdown(co, 5)).feed([true, false]);
+    assert!(iter.by_ref().eq([5, 4, 3]));
+    assert_eq!(iter.output(), None);
+
+    // The generator is left suspended once the iterator is exhausted
+    let (mut generator, resume_value) = iter.into_inner();
+    assert_eq!(resume_value, None);
+    assert!(!generator.is_completed());
+    assert!(matches!(generator.resume(false), GnState::Suspended(2)));
+}
+
+#[test]
+fn check_feed_into_inner_returns_pending_resume_value() {
+    let mut inputs = [true, false, true].into_iter();
+    let mut iter = local::Gn::new(|co| countdown(co, 5)).feed(inputs.by_ref());
+    assert_eq!(iter.next(), Some(5));
+
+    // The first input was already taken to resume the generator after 5
+    let (mut generator, resume_value) = iter.into_inner();
+    assert_eq!(resume_value, Some(true));
+    assert!(inputs.eq([false, true]));
+    assert!(matches!(
+        generator.resume(resume_value.unwrap()),
+        GnState::Suspended(4)
+    ));
+}
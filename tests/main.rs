@@ -1,7 +1,34 @@
 mod borrow;
+mod cancel;
+mod combinator;
 mod compiletest;
+mod completion;
+mod delegate;
+mod drive;
+mod duplex;
+mod effect;
+mod error;
+mod foreign;
 mod heap;
+mod iter;
 mod local;
+#[cfg(feature = "std")]
+mod panic;
+mod pipe;
+#[cfg(feature = "std")]
+mod pool;
+#[cfg(feature = "std")]
+mod prefetch;
+mod sched;
+mod send;
+mod session;
+#[cfg(feature = "std")]
+mod shared;
+mod size_hint;
 mod stack;
+mod start_with;
+mod stream;
 mod sync;
 mod tidy;
+mod typestate;
+mod util;
@@ -0,0 +1,13 @@
+use std::rc::Rc;
+
+use genoise::send;
+
+async fn my_generator(mut co: send::Co<'_, u32, ()>) {
+    let shared = Rc::new(1);
+    co.suspend(*shared).await;
+    drop(shared);
+}
+
+fn main() {
+    let _generator = send::Gn::new(my_generator);
+}
@@ -0,0 +1,13 @@
+use genoise::send;
+
+async fn my_generator(_co: send::Co<'_, (), ()>) {}
+
+fn main() {
+    let generator = send::Gn::new(my_generator);
+
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            assert!(!generator.started());
+        });
+    });
+}
@@ -0,0 +1,12 @@
+use genoise::local;
+use genoise::session::{Chan, End, Rx, Tx};
+
+type Ping = Tx<u32, Rx<u32, End>>;
+
+fn main() {
+    let _ = local::Gn::new(|co| async {
+        let chan = Chan::<Ping, _>::new(co);
+        let chan = chan.send(1).await;
+        chan.close(())
+    });
+}
@@ -0,0 +1,17 @@
+use genoise::local;
+use genoise::session::{Chan, End, Endpoint, Rx, Tx};
+
+type Ping = Tx<u32, Rx<u32, End>>;
+
+fn main() {
+    let generator = local::Gn::new(|co| async {
+        let chan = Chan::<Ping, _>::new(co);
+        let chan = chan.send(1).await;
+        let (_, chan) = chan.recv().await;
+        chan.close(())
+    });
+
+    let endpoint = Endpoint::new(generator);
+    let endpoint = endpoint.send(1);
+    let _ = endpoint.recv();
+}
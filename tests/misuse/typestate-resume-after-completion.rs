@@ -0,0 +1,17 @@
+use genoise::local;
+use genoise::typestate::{Step, UnstartedGn};
+
+async fn my_generator(mut co: local::Co<'_, u8, ()>) {
+    co.suspend(1).await;
+}
+
+fn main() {
+    let generator = UnstartedGn::new(my_generator);
+
+    let Step::Suspended(1, started) = generator.start() else {
+        panic!()
+    };
+
+    let _ = started.resume(());
+    let _ = started.resume(());
+}
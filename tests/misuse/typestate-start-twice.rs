@@ -0,0 +1,12 @@
+use genoise::local;
+use genoise::typestate::UnstartedGn;
+
+async fn my_generator(mut co: local::Co<'_, u8, ()>) {
+    co.suspend(1).await;
+}
+
+fn main() {
+    let generator = UnstartedGn::new(my_generator);
+    let _ = generator.start();
+    let _ = generator.start();
+}
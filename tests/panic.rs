@@ -0,0 +1,68 @@
+use genoise::{local, sync, CatchUnwindError, Co, GeneratorFlavor, GnError, GnState};
+
+async fn panicking<F: GeneratorFlavor>(mut co: Co<'_, u8, u8, F>) {
+    let value = co.suspend(0).await;
+
+    if value == 42 {
+        panic!("boom");
+    }
+
+    co.suspend(value).await;
+}
+
+#[test]
+fn check_panic_is_caught_local() {
+    let mut g = local::Gn::new(panicking);
+    assert!(matches!(g.start_catch_unwind(), Ok(GnState::Suspended(0))));
+
+    let Err(CatchUnwindError::Panicked(payload)) = g.resume_catch_unwind(42) else {
+        panic!("expected a panic");
+    };
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"boom"));
+    assert!(g.is_poisoned());
+    assert_eq!(g.try_resume(1).unwrap_err(), GnError::Poisoned);
+}
+
+#[test]
+fn check_panic_is_caught_sync() {
+    sync::let_gen!(g, panicking);
+    assert!(matches!(g.start_catch_unwind(), Ok(GnState::Suspended(0))));
+    let error = g.resume_catch_unwind(42).unwrap_err();
+    assert!(matches!(error, CatchUnwindError::Panicked(_)));
+    assert_eq!(error.to_string(), "generator panicked: boom");
+    assert!(g.is_poisoned());
+    assert_eq!(g.try_resume(1).unwrap_err(), GnError::Poisoned);
+}
+
+#[test]
+fn check_misuse_is_caught() {
+    let mut g = sync::Gn::new(panicking);
+    assert!(matches!(
+        g.resume_catch_unwind(0),
+        Err(CatchUnwindError::Generator(GnError::NotStarted))
+    ));
+    assert!(!g.is_poisoned());
+}
+
+#[test]
+fn check_generator_is_poisoned_when_panic_is_caught_elsewhere() {
+    let mut g = local::Gn::new(panicking);
+    assert!(matches!(g.start(), GnState::Suspended(0)));
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| g.resume(42)));
+    assert!(result.is_err());
+    assert!(g.is_poisoned());
+    assert!(matches!(
+        g.resume_catch_unwind(1),
+        Err(CatchUnwindError::Generator(GnError::Poisoned))
+    ));
+}
+
+#[test]
+#[should_panic(expected = "generator panicked during a previous execution step")]
+fn check_resume_poisoned_generator_panics() {
+    let mut g = sync::Gn::new(panicking);
+    let _ = g.start();
+    let _ = g.resume_catch_unwind(42);
+    g.resume(1);
+}
@@ -0,0 +1,137 @@
+use genoise::combinator::PipeOutput;
+use genoise::{local, sync, Co, Generator as _, GeneratorFlavor, GnError, GnState};
+
+async fn decode<F: GeneratorFlavor>(mut co: Co<'_, char, (), F>, input: &str) -> usize {
+    for c in input.chars() {
+        co.suspend(c).await;
+    }
+    input.len()
+}
+
+/// Yields `Some` each time a number is parsed, completes on `.`
+async fn parse<F: GeneratorFlavor>(mut co: Co<'_, Option<u32>, char, F>) -> u32 {
+    let mut c = co.suspend(None).await;
+    let mut number = 0;
+    let mut sum = 0;
+
+    loop {
+        if let Some(digit) = c.to_digit(10) {
+            number = number * 10 + digit;
+            c = co.suspend(None).await;
+        } else {
+            sum += number;
+            if c == '.' {
+                return sum;
+            }
+            c = co.suspend(Some(core::mem::take(&mut number))).await;
+        }
+    }
+}
+
+#[test]
+fn check_pipe_downstream_completes_first() {
+    let mut pipe = local::Gn::new(|co| decode(co, "12,3.45")).pipe(local::Gn::new(parse));
+    assert!(!pipe.started());
+    assert!(matches!(pipe.start(), GnState::Suspended(None)));
+    assert!(matches!(pipe.resume(()), GnState::Suspended(None)));
+    assert!(matches!(pipe.resume(()), GnState::Suspended(None)));
+    assert!(matches!(pipe.resume(()), GnState::Suspended(Some(12))));
+    assert!(matches!(pipe.resume(()), GnState::Suspended(None)));
+    assert!(matches!(
+        pipe.resume(()),
+        GnState::Completed(PipeOutput::Downstream(15))
+    ));
+    assert!(pipe.is_completed());
+    assert_eq!(pipe.try_resume(()).unwrap_err(), GnError::Completed);
+
+    let (mut upstream, downstream) = pipe.into_inner();
+    assert!(downstream.is_completed());
+    assert!(matches!(upstream.resume(()), GnState::Suspended('4')));
+}
+
+#[test]
+fn check_pipe_upstream_completes_first() {
+    local::let_gen!(upstream, |co| { decode(co, "7,") });
+    let mut pipe = upstream.pipe(sync::Gn::new(parse));
+    assert!(matches!(pipe.start(), GnState::Suspended(None)));
+    assert!(matches!(pipe.resume(()), GnState::Suspended(None)));
+    assert!(matches!(pipe.resume(()), GnState::Suspended(Some(7))));
+    assert!(matches!(
+        pipe.resume(()),
+        GnState::Completed(PipeOutput::Upstream(2usize))
+    ));
+
+    let (upstream, downstream) = pipe.into_inner();
+    assert!(upstream.is_completed());
+    assert!(!downstream.is_completed());
+}
+
+#[test]
+fn check_pipe_empty_upstream() {
+    let mut pipe = local::Gn::new(|co| decode(co, "")).pipe(local::Gn::new(parse));
+    assert!(matches!(pipe.start(), GnState::Suspended(None)));
+    assert!(matches!(
+        pipe.resume(()),
+        GnState::Completed(PipeOutput::Upstream(0))
+    ));
+
+    let (_, downstream) = pipe.into_inner();
+    assert!(downstream.started());
+    assert!(!downstream.is_completed());
+}
+
+#[test]
+fn check_pipe_downstream_completes_before_upstream_starts() {
+    let mut pipe = local::Gn::new(|co| decode(co, "1")).pipe(local::Gn::new(
+        |_: local::Co<'_, (), char>| async { "done" },
+    ));
+    assert!(matches!(
+        pipe.start(),
+        GnState::Completed(PipeOutput::Downstream("done"))
+    ));
+    assert!(!pipe.into_inner().0.started());
+}
+
+/// Receives three characters using plain suspensions, yielding the number received so far
+async fn collect_three(mut co: local::Co<'_, usize, char>) -> String {
+    let mut received = String::new();
+    loop {
+        received.push(co.suspend(received.len()).await);
+        if received.len() == 3 {
+            return received;
+        }
+    }
+}
+
+#[test]
+fn check_pipe_first_values_are_received() {
+    let mut pipe = local::Gn::new(|co| decode(co, "abcd")).pipe(local::Gn::new(collect_three));
+    // The value yielded by the downstream generator before receiving anything is not lost
+    assert!(matches!(pipe.start(), GnState::Suspended(0)));
+    assert!(matches!(pipe.resume(()), GnState::Suspended(1)));
+    assert!(matches!(pipe.resume(()), GnState::Suspended(2)));
+    let GnState::Completed(PipeOutput::Downstream(received)) = pipe.resume(()) else {
+        panic!("expected the downstream generator to complete");
+    };
+    assert_eq!(received, "abc");
+}
+
+/// Yields the start value, then each resume value
+async fn echo(mut co: local::Co<'_, char, char>) {
+    let mut c = co.take_start_value().expect("started without a value");
+    loop {
+        c = co.suspend(c).await;
+    }
+}
+
+#[test]
+fn check_pipe_first_resume_value_starts_upstream() {
+    let mut pipe = local::Gn::new(echo).pipe(local::Gn::new(collect_three));
+    assert!(matches!(pipe.start(), GnState::Suspended(0)));
+    assert!(matches!(pipe.resume('x'), GnState::Suspended(1)));
+    assert!(matches!(pipe.resume('y'), GnState::Suspended(2)));
+    let GnState::Completed(PipeOutput::Downstream(received)) = pipe.resume('z') else {
+        panic!("expected the downstream generator to complete");
+    };
+    assert_eq!(received, "xyz");
+}
@@ -0,0 +1,121 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+use std::time::Duration;
+
+use genoise::pool::ThreadPool;
+use genoise::sync::{Co, Gn, StaticGn};
+
+async fn sum(mut co: Co<'_, u64, u64>, count: u64) -> u64 {
+    let mut total = 0;
+    for i in 0..count {
+        total += co.suspend(i).await;
+    }
+    total
+}
+
+#[test]
+fn check_outputs() {
+    let pool = ThreadPool::with_threads(3);
+    assert_eq!(pool.threads(), 3);
+
+    let handles: Vec<_> = (0..50u64)
+        .map(|count| pool.spawn(Gn::new(move |co| sum(co, count)), |i| i * 2))
+        .collect();
+
+    for (count, handle) in (0..50u64).zip(handles) {
+        assert_eq!(handle.join().unwrap(), count * count.saturating_sub(1));
+    }
+}
+
+#[test]
+fn check_tasks_run_on_workers() {
+    let pool = ThreadPool::with_threads(4);
+    let threads = Arc::new(Mutex::new(HashSet::<ThreadId>::new()));
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let threads = Arc::clone(&threads);
+            let generator: StaticGn<(), (), ()> = Gn::new(|mut co| async move {
+                for _ in 0..200 {
+                    co.suspend(()).await;
+                }
+            });
+            pool.spawn(generator, move |()| {
+                threads.lock().unwrap().insert(thread::current().id());
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert!(!threads.lock().unwrap().contains(&thread::current().id()));
+}
+
+#[test]
+fn check_tasks_migrate_between_workers() {
+    let pool = ThreadPool::with_threads(2);
+
+    // Three tasks on two workers: the worker owning a single task runs out of work first, and
+    // steals a task already stepped by the other worker
+    let steps: Vec<_> = (0..3)
+        .map(|_| Arc::new(Mutex::new(Vec::<ThreadId>::new())))
+        .collect();
+
+    let handles: Vec<_> = steps
+        .iter()
+        .map(|steps| {
+            let steps = Arc::clone(steps);
+            let generator: StaticGn<(), (), ()> = Gn::new(|mut co| async move {
+                for _ in 0..50 {
+                    co.suspend(()).await;
+                }
+            });
+            pool.spawn(generator, move |()| {
+                steps.lock().unwrap().push(thread::current().id());
+                thread::sleep(Duration::from_millis(1));
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let workers: Vec<HashSet<ThreadId>> = steps
+        .iter()
+        .map(|steps| steps.lock().unwrap().iter().copied().collect())
+        .collect();
+
+    assert!(workers.iter().any(|workers| workers.len() > 1));
+}
+
+#[test]
+fn check_panic_is_forwarded() {
+    let pool = ThreadPool::with_threads(2);
+
+    let faulty = pool.spawn(
+        Gn::new(|mut co: Co<'_, u32, ()>| async move {
+            co.suspend(1).await;
+            panic!("oops");
+        }),
+        |_| (),
+    );
+    let healthy = pool.spawn(Gn::new(|co| sum(co, 3)), |i| i);
+
+    let payload = faulty.join().unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"oops"));
+    assert_eq!(healthy.join().unwrap(), 3);
+}
+
+#[test]
+fn check_drop_waits_for_tasks() {
+    let pool = ThreadPool::with_threads(2);
+    let handle = pool.spawn(Gn::new(|co| sum(co, 1000)), |_| 1);
+    drop(pool);
+
+    assert!(handle.is_finished());
+    assert_eq!(handle.join().unwrap(), 1000);
+}
@@ -0,0 +1,78 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use genoise::sync::{Gn, StaticGn};
+
+fn counter(produced: Arc<AtomicUsize>, count: usize) -> StaticGn<usize, (), usize> {
+    Gn::new(move |mut co| async move {
+        for i in 0..count {
+            produced.fetch_add(1, Ordering::SeqCst);
+            co.suspend(i).await;
+        }
+        count
+    })
+}
+
+#[test]
+fn check_values_and_output() {
+    let produced = Arc::new(AtomicUsize::new(0));
+    let mut prefetch = counter(produced, 5).spawn_ahead(2);
+
+    assert_eq!(prefetch.by_ref().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+    assert_eq!(prefetch.output(), Some(&5));
+    assert_eq!(prefetch.next(), None);
+    assert_eq!(prefetch.into_output(), 5);
+}
+
+#[test]
+fn check_backpressure() {
+    let produced = Arc::new(AtomicUsize::new(0));
+    let mut prefetch = counter(Arc::clone(&produced), 100).spawn_ahead(3);
+
+    assert_eq!(prefetch.next(), Some(0));
+    thread::sleep(Duration::from_millis(50));
+
+    // At most one value consumed, three buffered, and one blocked on send
+    assert!(produced.load(Ordering::SeqCst) <= 5);
+}
+
+#[test]
+fn check_into_output_drains() {
+    let produced = Arc::new(AtomicUsize::new(0));
+    let prefetch = counter(Arc::clone(&produced), 10).spawn_ahead(1);
+
+    assert_eq!(prefetch.into_output(), 10);
+    assert_eq!(produced.load(Ordering::SeqCst), 10);
+}
+
+#[test]
+fn check_drop_stops_generator() {
+    let produced = Arc::new(AtomicUsize::new(0));
+    let mut prefetch = counter(Arc::clone(&produced), usize::MAX).spawn_ahead(0);
+
+    assert_eq!(prefetch.next(), Some(0));
+    drop(prefetch);
+
+    let stopped = produced.load(Ordering::SeqCst);
+    thread::sleep(Duration::from_millis(20));
+    assert_eq!(produced.load(Ordering::SeqCst), stopped);
+}
+
+#[test]
+fn check_panic_is_forwarded() {
+    let generator: StaticGn<u32, (), ()> = Gn::new(|mut co| async move {
+        co.suspend(1).await;
+        panic!("oops");
+    });
+    let mut prefetch = generator.spawn_ahead(4);
+
+    assert_eq!(prefetch.next(), Some(1));
+
+    let payload = catch_unwind(AssertUnwindSafe(|| prefetch.next())).unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"oops"));
+    assert_eq!(prefetch.next(), None);
+    assert_eq!(prefetch.output(), None);
+}
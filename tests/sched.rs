@@ -0,0 +1,68 @@
+use genoise::sched::Scheduler;
+use genoise::{local, Co, GeneratorFlavor};
+
+async fn worker<F: GeneratorFlavor>(mut co: Co<'_, usize, usize, F>, steps: usize) -> usize {
+    let mut total = 0;
+    for step in 0..steps {
+        total += co.suspend(step).await;
+    }
+    total
+}
+
+#[test]
+fn check_round_robin() {
+    let mut scheduler = Scheduler::new();
+    let a = scheduler.spawn(local::Gn::new(|co| worker(co, 2)), |step| step + 1);
+    let b = scheduler.spawn(local::Gn::new(|co| worker(co, 3)), |step| step * 10);
+    assert_eq!(scheduler.len(), 2);
+
+    let mut trace = Vec::new();
+    while let Some(id) = scheduler.run_one() {
+        trace.push(id);
+    }
+
+    assert_eq!(
+        trace,
+        [a.id(), b.id(), a.id(), b.id(), a.id(), b.id(), b.id()]
+    );
+    assert!(scheduler.is_empty());
+    assert!(a.is_finished());
+    assert_eq!(a.take_output(), Some(3));
+    assert_eq!(a.take_output(), None);
+    assert_eq!(b.take_output(), Some(30));
+}
+
+#[test]
+fn check_priority() {
+    let mut scheduler = Scheduler::new();
+    let low = scheduler.spawn_with_priority(-1, local::Gn::new(|co| worker(co, 1)), |_| 0);
+    let normal = scheduler.spawn(local::Gn::new(|co| worker(co, 1)), |_| 0);
+    let high = scheduler.spawn_with_priority(5, local::Gn::new(|co| worker(co, 1)), |_| 0);
+
+    let mut trace = Vec::new();
+    while let Some(id) = scheduler.run_one() {
+        trace.push(id);
+    }
+
+    let (low, normal, high) = (low.id(), normal.id(), high.id());
+    assert_eq!(trace, [high, high, normal, normal, low, low]);
+}
+
+#[test]
+fn check_per_task_handler_and_borrows() {
+    let mut log = Vec::new();
+    let input = [1, 2, 3];
+
+    {
+        local::let_gen!(stacked, |co| { worker(co, input.len()) });
+        let mut scheduler = Scheduler::new();
+        let handle = scheduler.spawn(&mut stacked, |step| {
+            log.push(step);
+            input[step]
+        });
+        scheduler.run();
+        assert_eq!(handle.take_output(), Some(6usize));
+    }
+
+    assert_eq!(log, [0, 1, 2]);
+}
@@ -0,0 +1,109 @@
+use std::cell::{Cell, RefCell};
+use std::sync::mpsc;
+use std::thread;
+
+use genoise::send::{Co, Gn, StackCo, StackGn};
+use genoise::{send, GnState};
+
+macro_rules! assert_send {
+    ($type:ty) => {
+        const _: fn() = || {
+            fn assert_impl<T: Send>() {}
+            assert_impl::<$type>();
+        };
+    };
+}
+
+#[test]
+fn check_heap_gn_is_send() {
+    assert_send!(Gn<'_, '_, (), (), ()>);
+}
+
+#[test]
+fn check_stack_gn_is_send() {
+    assert_send!(StackGn<'_, '_, (), (), ()>);
+}
+
+async fn running_total(mut co: Co<'_, u32, u32>) -> u32 {
+    let total = Cell::new(0);
+    let history = RefCell::new(Vec::new());
+
+    loop {
+        let value = co.suspend(total.get()).await;
+        if value == 0 {
+            break;
+        }
+        history.borrow_mut().push(value);
+        total.set(total.get() + value);
+    }
+
+    let count = history.borrow().len();
+    u32::try_from(count).unwrap()
+}
+
+#[test]
+fn check_cell_held_across_suspend() {
+    let mut generator = Gn::new(running_total);
+    assert!(matches!(generator.start(), GnState::Suspended(0)));
+
+    let mut generator = thread::spawn(move || {
+        assert!(matches!(generator.resume(5), GnState::Suspended(5)));
+        generator
+    })
+    .join()
+    .unwrap();
+
+    assert!(matches!(generator.resume(2), GnState::Suspended(7)));
+    assert!(matches!(generator.resume(0), GnState::Completed(2)));
+}
+
+#[test]
+fn check_receiver_held_across_suspend() {
+    let (sender, receiver) = mpsc::channel::<String>();
+
+    let mut generator = Gn::new(|mut co: Co<'_, String, ()>| async move {
+        while let Ok(message) = receiver.recv() {
+            co.suspend(message).await;
+        }
+    });
+
+    sender.send("hello".to_owned()).unwrap();
+    sender.send("world".to_owned()).unwrap();
+    drop(sender);
+
+    let messages = thread::spawn(move || {
+        let mut messages = Vec::new();
+        let mut state = generator.start();
+        while let GnState::Suspended(message) = state {
+            messages.push(message);
+            state = generator.resume(());
+        }
+        messages
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(messages, ["hello", "world"]);
+}
+
+async fn stacked(mut co: StackCo<'_, u32, ()>) -> u32 {
+    let count = Cell::new(0);
+    co.suspend(1).await;
+    count.set(count.get() + 1);
+    co.suspend(2).await;
+    count.set(count.get() + 1);
+    count.get()
+}
+
+#[test]
+fn check_stack_flavor() {
+    send::let_gen!(generator, stacked);
+    assert!(matches!(generator.start(), GnState::Suspended(1u32)));
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            assert!(matches!(generator.resume(()), GnState::Suspended(2)));
+            assert!(matches!(generator.resume(()), GnState::Completed(2u32)));
+        });
+    });
+}
@@ -0,0 +1,97 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use genoise::session::{Chan, Closed, End, Endpoint, Erase, Message, Rx, SessionFlavor, Tx};
+use genoise::{local, sync, Co};
+
+/// Client side of the protocol: sends a name and a count, receives one greeting per count
+type Greet = Tx<String, Tx<u32, Rx<Vec<String>, End>>>;
+
+async fn greet_client<F>(
+    co: Co<'_, Option<Message<F>>, Option<Message<F>>, F>,
+) -> Closed<Greet, usize>
+where
+    F: SessionFlavor,
+    F::Payload: Erase<String> + Erase<u32>,
+{
+    let chan = Chan::<Greet, _>::new(co);
+    let chan = chan.send("ferris".to_owned()).await;
+    let chan = chan.send(2).await;
+    let (greetings, chan) = chan.recv().await;
+    chan.close(greetings.len())
+}
+
+#[test]
+fn check_session_heap() {
+    let endpoint = Endpoint::new(local::Gn::new(greet_client));
+    let (name, endpoint) = endpoint.recv();
+    let (count, endpoint) = endpoint.recv();
+    let endpoint = endpoint.send(vec![format!("hello {name}"); count as usize]);
+    assert_eq!(endpoint.close(), 2);
+}
+
+#[test]
+fn check_session_sync_thread() {
+    let generator = sync::Gn::new(greet_client);
+    let handle = std::thread::spawn(move || {
+        let (name, endpoint) = Endpoint::new(generator).recv();
+        let (_, endpoint) = endpoint.recv();
+        endpoint.send(vec![name]).close()
+    });
+    assert_eq!(handle.join().unwrap(), 1);
+}
+
+#[test]
+fn check_session_stack() {
+    local::let_gen!(generator, greet_client);
+    let (_, endpoint) = Endpoint::new(&mut generator).recv();
+    let (_, endpoint) = endpoint.recv();
+    assert_eq!(endpoint.send(Vec::new()).close(), 0usize);
+    assert!(generator.is_completed());
+}
+
+#[test]
+#[should_panic = "generator resumed without a message"]
+fn check_session_without_endpoint() {
+    let mut generator = local::Gn::new(greet_client);
+    generator.start();
+    generator.resume(None);
+    generator.resume(None);
+    generator.resume(None);
+}
+
+#[test]
+#[should_panic = "generator does not follow the session protocol"]
+fn check_session_after_raw_suspend() {
+    async fn client(
+        mut co: Co<
+            '_,
+            Option<Message<local::HeapLocal>>,
+            Option<Message<local::HeapLocal>>,
+            local::HeapLocal,
+        >,
+    ) -> Closed<Greet, usize> {
+        co.suspend(None).await;
+        greet_client(co).await
+    }
+
+    let _ = Endpoint::new(local::Gn::new(client)).recv();
+}
+
+/// Client side of a protocol exchanging values which can't be sent to another thread
+type Share = Tx<Rc<Cell<u32>>, Rx<Rc<Cell<u32>>, End>>;
+
+#[test]
+fn check_session_local_payloads() {
+    let endpoint = Endpoint::new(local::Gn::new(|co| async {
+        let chan = Chan::<Share, _>::new(co);
+        let counter = Rc::new(Cell::new(1));
+        let chan = chan.send(Rc::clone(&counter)).await;
+        let (received, chan) = chan.recv().await;
+        chan.close(Rc::ptr_eq(&counter, &received))
+    }));
+
+    let (counter, endpoint) = endpoint.recv();
+    counter.set(counter.get() + 1);
+    assert!(endpoint.send(counter).close());
+}
@@ -0,0 +1,93 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::thread;
+
+use genoise::sync::{Gn, SharedGn};
+
+fn jobs(count: u32) -> SharedGn<'static, 'static, u32, &'static str> {
+    SharedGn::new(Gn::new(move |mut co| async move {
+        for job in 0..count {
+            co.suspend(job).await;
+        }
+        "done"
+    }))
+}
+
+#[test]
+fn check_each_value_is_pulled_once() {
+    let jobs = jobs(1000);
+
+    let workers: Vec<_> = (0..8)
+        .map(|_| {
+            let jobs = jobs.clone();
+            thread::spawn(move || jobs.collect::<Vec<_>>())
+        })
+        .collect();
+
+    let mut pulled: Vec<u32> = workers
+        .into_iter()
+        .flat_map(|worker| worker.join().unwrap())
+        .collect();
+    pulled.sort_unstable();
+
+    assert_eq!(pulled, (0..1000).collect::<Vec<_>>());
+    assert_eq!(jobs.take_output(), Some("done"));
+    assert_eq!(jobs.take_output(), None);
+}
+
+#[test]
+fn check_contended_consumers_do_not_panic() {
+    let jobs = jobs(10_000);
+
+    let workers: Vec<_> = (0..8)
+        .map(|_| {
+            let jobs = jobs.clone();
+            thread::spawn(move || {
+                catch_unwind(AssertUnwindSafe(|| {
+                    jobs.inspect(|_| thread::yield_now()).count()
+                }))
+            })
+        })
+        .collect();
+
+    let pulled: usize = workers
+        .into_iter()
+        .map(|worker| worker.join().unwrap().expect("a consumer panicked"))
+        .sum();
+
+    assert_eq!(pulled, 10_000);
+    assert_eq!(jobs.take_output(), Some("done"));
+}
+
+#[test]
+fn check_clones_share_progress() {
+    let mut first = jobs(3);
+    let mut second = first.clone();
+
+    assert_eq!(first.next(), Some(0));
+    assert_eq!(second.next(), Some(1));
+    assert_eq!(first.next(), Some(2));
+    assert_eq!(second.take_output(), None);
+    assert_eq!(second.next(), None);
+    assert_eq!(first.next(), None);
+    assert_eq!(first.take_output(), Some("done"));
+}
+
+#[test]
+fn check_panic_is_reported_to_other_consumers() {
+    let mut first = SharedGn::new(Gn::new(|mut co| async move {
+        co.suspend(1u32).await;
+        panic!("oops");
+    }));
+    let mut second = first.clone();
+
+    assert_eq!(first.next(), Some(1));
+
+    let payload = catch_unwind(AssertUnwindSafe(|| first.next())).unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"oops"));
+
+    let payload = catch_unwind(AssertUnwindSafe(|| second.next())).unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<String>().map(String::as_str),
+        Some("generator panicked during a previous execution step")
+    );
+}
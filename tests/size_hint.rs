@@ -0,0 +1,118 @@
+use std::thread;
+
+use genoise::{local, sync, Co, GeneratorFlavor, GnState};
+
+async fn repeat<F: GeneratorFlavor>(mut co: Co<'_, u8, (), F>, value: u8, count: usize) {
+    co.set_size_hint(count, Some(count));
+
+    for _ in 0..count {
+        co.suspend(value).await;
+    }
+}
+
+#[test]
+fn check_default_size_hint() {
+    let generator = local::Gn::new(|mut co: local::Co<'_, u8, ()>| async move {
+        co.suspend(0).await;
+    });
+    assert_eq!(generator.size_hint(), (0, None));
+}
+
+#[test]
+fn check_size_hint_is_decremented_local() {
+    local::let_gen!(generator, |co| { repeat(co, 1, 3) });
+    assert_eq!(generator.size_hint(), (0, None));
+    assert_eq!(generator.next(), Some(1));
+    assert_eq!(generator.size_hint(), (2, Some(2)));
+    assert_eq!(generator.next(), Some(1));
+    assert_eq!(generator.next(), Some(1));
+    assert_eq!(generator.size_hint(), (0, Some(0)));
+    assert_eq!(generator.next(), None);
+    assert_eq!(generator.size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn check_size_hint_sync() {
+    let mut generator = sync::Gn::new(|co| repeat(co, 7, 2));
+    assert_eq!(generator.next(), Some(7));
+    assert_eq!(generator.size_hint(), (1, Some(1)));
+    assert_eq!(generator.collect::<Vec<_>>(), [7]);
+}
+
+#[test]
+fn check_size_hint_can_be_updated() {
+    let mut generator = local::Gn::new(|mut co: local::Co<'_, u8, bool>| async move {
+        co.set_size_hint(1, None);
+
+        if co.suspend(0).await {
+            co.set_size_hint(2, Some(2));
+            co.suspend(1).await;
+            co.suspend(2).await;
+        }
+    });
+
+    assert!(matches!(generator.start(), GnState::Suspended(0)));
+    assert_eq!(generator.yield_hint(), (0, None));
+    assert!(matches!(generator.resume(true), GnState::Suspended(1)));
+    assert_eq!(generator.yield_hint(), (1, Some(1)));
+}
+
+#[test]
+fn check_size_hint_of_iterator_adapters() {
+    let mut iter = local::Gn::new(|co| repeat(co, 1, 3)).into_iter_with_output();
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.size_hint(), (2, Some(2)));
+
+    let generator = local::Gn::new(|mut co: local::Co<'_, u8, bool>| async move {
+        co.set_size_hint(3, Some(3));
+        while co.suspend(0).await {}
+    });
+
+    let mut iter = generator.feed([true]);
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.size_hint(), (2, Some(2)));
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn check_size_hint_declared_before_start() {
+    let generator = local::Gn::new(|mut co: local::Co<'_, u8, ()>| async move {
+        for value in 0..4 {
+            co.suspend(value).await;
+        }
+    })
+    .with_size_hint(4, Some(4));
+
+    assert_eq!(generator.size_hint(), (4, Some(4)));
+
+    let values = Vec::from_iter(generator);
+    assert_eq!(values, [0, 1, 2, 3]);
+}
+
+#[test]
+fn check_size_hint_declared_before_start_can_be_updated() {
+    let mut generator = sync::Gn::new(|co| repeat(co, 3, 2)).with_size_hint(0, None);
+    assert_eq!(generator.yield_hint(), (0, None));
+    assert_eq!(generator.next(), Some(3));
+    assert_eq!(generator.yield_hint(), (1, Some(1)));
+}
+
+#[test]
+fn check_size_hint_survives_concurrent_reads() {
+    let generator = sync::Gn::new(|co| repeat(co, 1, 3)).with_size_hint(3, Some(3));
+
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..10_000 {
+                    let hint = generator.yield_hint();
+                    // A read may fail on contention, but must never clobber the hint
+                    assert!(hint == (3, Some(3)) || hint == (0, None));
+                }
+            });
+        }
+    });
+
+    assert_eq!(generator.yield_hint(), (3, Some(3)));
+}
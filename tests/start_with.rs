@@ -0,0 +1,52 @@
+use genoise::{local, sync, Co, GeneratorFlavor, GnError, GnState};
+
+async fn doubler<F: GeneratorFlavor>(mut co: Co<'_, u32, u32, F>) -> u32 {
+    let mut input = co.take_start_value().expect("started with a value");
+    let mut count = 0;
+
+    while input != 0 {
+        count += 1;
+        input = co.suspend(input * 2).await;
+    }
+
+    count
+}
+
+#[test]
+fn check_start_value_is_received_local() {
+    local::let_gen!(g, doubler);
+    assert!(matches!(g.start_with(1), GnState::Suspended(2)));
+    assert!(matches!(g.resume(5), GnState::Suspended(10)));
+    assert!(matches!(g.resume(0), GnState::Completed(2u32)));
+}
+
+#[test]
+fn check_start_value_is_received_sync() {
+    let mut g = sync::Gn::new(doubler);
+    assert!(matches!(g.start_with(0), GnState::Completed(0)));
+}
+
+#[test]
+fn check_start_value_is_discarded_after_suspension() {
+    let mut g = local::Gn::new(|mut co: local::Co<'_, (), u8>| async move {
+        co.suspend(()).await;
+        co.take_start_value()
+    });
+
+    assert!(matches!(g.start_with(1), GnState::Suspended(())));
+    assert!(matches!(g.resume(2), GnState::Completed(None)));
+}
+
+#[test]
+fn check_no_start_value() {
+    let mut g =
+        local::Gn::new(|mut co: local::Co<'_, (), u8>| async move { co.take_start_value() });
+    assert!(matches!(g.start(), GnState::Completed(None)));
+}
+
+#[test]
+fn check_start_with_twice_is_an_error() {
+    let mut g = local::Gn::new(doubler);
+    assert!(matches!(g.try_start_with(1), Ok(GnState::Suspended(2))));
+    assert_eq!(g.try_start_with(1).unwrap_err(), GnError::AlreadyStarted);
+}
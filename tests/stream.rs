@@ -0,0 +1,90 @@
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use genoise::stream::Stream;
+use genoise::{local, sync, Co, GeneratorFlavor, GnError, GnState};
+
+use crate::util::{block_on, block_on_counting, CountingWaker, YieldNow};
+
+async fn slow_numbers<F: GeneratorFlavor>(mut co: Co<'_, u32, (), F>) {
+    for i in 0..3 {
+        YieldNow::new().await;
+        co.suspend(i).await;
+    }
+}
+
+#[test]
+fn check_stream() {
+    let mut generator = local::Gn::new(slow_numbers).into_async();
+    let (values, wake_ups) = block_on_counting(async {
+        let mut values = Vec::new();
+        while let Some(value) = generator.next().await {
+            values.push(value);
+        }
+        values
+    });
+    assert_eq!(values, [0, 1, 2]);
+    assert_eq!(wake_ups, 3);
+    assert!(generator.is_completed());
+    assert_eq!(block_on(generator.next()), None);
+}
+
+async fn add<F: GeneratorFlavor>(mut co: Co<'_, u32, u32, F>) -> u32 {
+    let first = co.take_start_value().unwrap_or_default();
+    YieldNow::new().await;
+    let second = co.suspend(first).await;
+    YieldNow::new().await;
+    first + second
+}
+
+#[test]
+fn check_resume_async() {
+    let mut generator = sync::Gn::new(add).into_async();
+    let (states, wake_ups) = block_on_counting(async {
+        let first = generator.try_start_with(2).await;
+        let second = generator.try_resume(3).await;
+        (first, second)
+    });
+    assert!(matches!(states.0, Ok(GnState::Suspended(2))));
+    assert!(matches!(states.1, Ok(GnState::Completed(5))));
+    assert_eq!(wake_ups, 2);
+    assert_eq!(
+        block_on(generator.try_resume(0)).unwrap_err(),
+        GnError::Completed
+    );
+}
+
+#[test]
+fn check_dropped_step() {
+    local::let_gen!(generator, add);
+    let mut generator = generator.into_async();
+
+    let waker = Waker::from(CountingWaker::new());
+    let mut context = Context::from_waker(&waker);
+    assert!(pin!(generator.try_start()).poll(&mut context).is_pending());
+    assert!(generator.is_in_progress());
+
+    assert_eq!(
+        block_on(generator.try_resume(1)).unwrap_err(),
+        GnError::StepInProgress
+    );
+    assert!(matches!(
+        generator.poll_step(&mut context),
+        Poll::Ready(Ok(GnState::Suspended(0u32)))
+    ));
+    assert!(!generator.is_in_progress());
+    assert!(matches!(
+        generator.poll_step(&mut context),
+        Poll::Ready(Err(GnError::NoStepInProgress))
+    ));
+
+    let mut generator = generator.into_inner();
+    assert_eq!(generator.try_resume(1).unwrap_err(), GnError::ForeignFuture);
+}
+
+#[test]
+fn check_foreign_future_rejected_when_sync() {
+    let mut generator = local::Gn::new(slow_numbers);
+    assert_eq!(generator.try_start().unwrap_err(), GnError::ForeignFuture);
+}
@@ -0,0 +1,75 @@
+use core::pin::pin;
+
+use genoise::typestate::{Step, UnstartedGn};
+use genoise::{local, sync, Co, GeneratorFlavor};
+
+async fn accumulate<F: GeneratorFlavor>(mut co: Co<'_, u32, u32, F>) -> u32 {
+    let mut total = 0;
+
+    while total < 10 {
+        total += co.suspend(total).await;
+    }
+
+    total
+}
+
+fn drive<F: GeneratorFlavor>(generator: UnstartedGn<'_, '_, u32, u32, u32, F>) -> u32 {
+    let mut step = generator.start();
+
+    loop {
+        step = match step {
+            Step::Suspended(total, generator) => generator.resume(total + 1),
+            Step::Completed(total) => break total,
+        };
+    }
+}
+
+#[test]
+fn check_typestate_local() {
+    let slot = local::StackCellSlot::default();
+    let fut = pin!(accumulate(local::StackCo::new_stacked(&slot)));
+    let generator = UnstartedGn::<_, _, _, local::StackLocal>::from_parts(&slot, fut);
+    assert_eq!(drive(generator), 15);
+    assert_eq!(drive(UnstartedGn::new(accumulate)), 15);
+}
+
+#[test]
+fn check_typestate_sync() {
+    let slot = sync::StackCellSlot::default();
+    let fut = pin!(accumulate(sync::StackCo::new_stacked(&slot)));
+    let generator = UnstartedGn::<_, _, _, sync::StackSync>::from_parts(&slot, fut);
+    assert_eq!(drive(generator), 15);
+    assert_eq!(drive(UnstartedGn::new_sync(accumulate)), 15);
+}
+
+#[test]
+fn check_typestate_start_with() {
+    let generator = UnstartedGn::new(|mut co: local::Co<'_, u32, u32>| async move {
+        let first = co.take_start_value().unwrap();
+        first + co.suspend(first).await
+    });
+
+    let Step::Suspended(3, generator) = generator.start_with(3) else {
+        panic!()
+    };
+
+    assert!(matches!(generator.resume(4), Step::Completed(7)));
+}
+
+#[test]
+fn check_started_gn_into_inner() {
+    let Step::Suspended(0, generator) = UnstartedGn::new(accumulate).start() else {
+        panic!()
+    };
+
+    let mut generator = generator.into_inner();
+    assert!(generator.started());
+    assert!(generator.try_start().is_err());
+}
+
+#[test]
+fn check_unstarted_gn_into_inner() {
+    let mut generator = UnstartedGn::new(accumulate).into_inner();
+    assert!(!generator.started());
+    assert!(generator.try_start().is_ok());
+}
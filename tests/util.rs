@@ -0,0 +1,94 @@
+//! Helpers shared by the tests driving generators from async code
+
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+/// Counts how many times the waker was woken up
+pub struct CountingWaker(AtomicUsize);
+
+impl CountingWaker {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self(AtomicUsize::new(0)))
+    }
+
+    pub fn wake_ups(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Polls a future to completion, assuming it is woken up right away when pending
+pub fn block_on<T>(future: impl Future<Output = T>) -> T {
+    block_on_counting(future).0
+}
+
+/// Polls a future to completion, returning the number of wake ups
+pub fn block_on_counting<T>(future: impl Future<Output = T>) -> (T, usize) {
+    let counter = CountingWaker::new();
+    let waker = Waker::from(Arc::clone(&counter));
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return (output, counter.wake_ups());
+        }
+    }
+}
+
+/// Pending once, waking the waker up right away, like a runtime's `yield_now`
+pub struct YieldNow(bool);
+
+impl YieldNow {
+    pub fn new() -> Self {
+        Self(false)
+    }
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+/// Resolves to its value after being pending once
+pub struct Delayed<T> {
+    value: Option<T>,
+    yield_now: YieldNow,
+}
+
+impl<T> Delayed<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value: Some(value),
+            yield_now: YieldNow::new(),
+        }
+    }
+}
+
+impl<T: Unpin> Future for Delayed<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        match Pin::new(&mut self.yield_now).poll(cx) {
+            Poll::Ready(()) => Poll::Ready(self.value.take().expect("polled after completion")),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}